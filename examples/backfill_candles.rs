@@ -0,0 +1,231 @@
+extern crate chrono;
+extern crate gdax_rs;
+extern crate tokio_core;
+
+extern crate csv;
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::process;
+
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use tokio_core::reactor::Core;
+
+use gdax_rs::RESTClient;
+use gdax_rs::error::RestError;
+use gdax_rs::products::{GetHistoricRates, Granularity};
+
+/// Returns the length of a file without its incomplete last line, the file is read backwards
+/// from its end up to the last line break.
+fn complete_length(file: &mut File) -> io::Result<u64> {
+    let mut length = file.seek(SeekFrom::End(0))?;
+    let mut byte = [0u8; 1];
+    while length > 0 {
+        file.seek(SeekFrom::Start(length - 1))?;
+        file.read_exact(&mut byte)?;
+        if byte[0] == b'\n' {
+            break;
+        }
+        length -= 1;
+    }
+    Ok(length)
+}
+
+/// Returns the times of the candles already saved in `path`.
+///
+/// If the previous run was interrupted in the middle of a line, the incomplete line is removed
+/// so that the new candles are appended to a valid file.
+fn load_times(path: &str) -> BTreeSet<i64> {
+    let mut times = BTreeSet::new();
+    if !Path::new(path).exists() {
+        return times;
+    }
+
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .unwrap();
+    let valid_length = complete_length(&mut file).unwrap();
+    if valid_length < file.metadata().unwrap().len() {
+        println!("Removing the incomplete last line of {}", path);
+        file.set_len(valid_length).unwrap();
+    }
+    file.seek(SeekFrom::Start(0)).unwrap();
+
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader(file);
+    for record in reader.records() {
+        if let Some(time) = record.ok().and_then(|record| record[0].parse().ok()) {
+            times.insert(time);
+        }
+    }
+    times
+}
+
+/// Returns the candle times between `start` and `end` which are not in `times`, grouped in
+/// intervals `[first, last]`.
+fn missing_intervals(
+    times: &BTreeSet<i64>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    granularity: Granularity,
+) -> Vec<(i64, i64)> {
+    let step = granularity.seconds();
+    // the candles start on a multiple of the granularity
+    let first = (start.timestamp() + step - 1) / step * step;
+
+    let mut intervals: Vec<(i64, i64)> = Vec::new();
+    let mut time = first;
+    while time < end.timestamp() {
+        if !times.contains(&time) {
+            match intervals.last_mut() {
+                Some(interval) if interval.1 + step == time => interval.1 = time,
+                _ => intervals.push((time, time)),
+            }
+        }
+        time += step;
+    }
+    intervals
+}
+
+/// Download the candles missing in `path`, it stops at the first request which fails. The
+/// candles received before are kept so the download can be resumed later.
+fn backfill_candles(
+    core: &mut Core,
+    client: &mut RESTClient,
+    product: &str,
+    granularity: Granularity,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    path: &str,
+) -> Result<(), RestError> {
+    let mut times = load_times(path);
+    let gaps = missing_intervals(&times, start, end, granularity);
+    println!(
+        "{} candles already in {}, downloading {} gaps of {}, please be patient...",
+        times.len(),
+        path,
+        gaps.len(),
+        product
+    );
+
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .unwrap();
+    let mut wrt = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(file);
+
+    let mut result = Ok(());
+    'gaps: for (first, last) in gaps {
+        let windows = GetHistoricRates::windows(
+            product,
+            Utc.timestamp(first, 0),
+            Utc.timestamp(last, 0) + granularity.duration(),
+            granularity,
+        );
+        for window in windows {
+            let candles = match core.run(client.send_request(&window)) {
+                Ok(candles) => candles,
+                Err(error) => {
+                    println!("The download has been interrupted: {}", error);
+                    result = Err(error);
+                    break 'gaps;
+                }
+            };
+            for candle in candles.iter().rev() {
+                let time = candle.time.timestamp();
+                if time >= first && time <= last && times.insert(time) {
+                    wrt.serialize(candle).unwrap();
+                }
+            }
+            // the file is complete after each window, the download can be stopped at any time
+            wrt.flush().unwrap();
+        }
+    }
+    wrt.flush().unwrap();
+
+    // after an interruption the gaps are not known to be empty
+    let message = if result.is_ok() {
+        "No data"
+    } else {
+        "Not downloaded yet"
+    };
+    for (first, last) in missing_intervals(&times, start, end, granularity) {
+        println!(
+            "{} from {} to {}",
+            message,
+            Utc.timestamp(first, 0),
+            Utc.timestamp(last, 0)
+        );
+    }
+    result
+}
+
+fn parse_granularity(seconds: &str) -> Option<Granularity> {
+    match seconds {
+        "60" => Some(Granularity::OneMinute),
+        "300" => Some(Granularity::FiveMinutes),
+        "900" => Some(Granularity::FifteenMinutes),
+        "3600" => Some(Granularity::OneHour),
+        "21600" => Some(Granularity::SixHours),
+        "86400" => Some(Granularity::OneDay),
+        _ => None,
+    }
+}
+
+/// Parse a date (`2017-12-21`) or a RFC 3339 date time (`2017-12-21T10:00:00Z`)
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    date.parse().ok().or_else(|| {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .ok()
+            .map(|date| Utc.from_utc_date(&date).and_hms(0, 0, 0))
+    })
+}
+
+fn main() {
+    let mut core = Core::new().unwrap();
+    let handle = core.handle();
+
+    let mut test_client = RESTClient::default(&handle);
+
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() > 4 {
+        let granularity = parse_granularity(&args[2])
+            .expect("The granularity must be 60, 300, 900, 3600, 21600 or 86400");
+        let start = parse_date(&args[3]).expect("Invalid start date");
+        let end = parse_date(&args[4]).expect("Invalid end date");
+
+        let path = if args.len() > 5 {
+            args[5].to_owned()
+        } else {
+            format!("{}-{}.csv", args[1], args[2])
+        };
+
+        let result = backfill_candles(
+            &mut core,
+            &mut test_client,
+            &args[1],
+            granularity,
+            start,
+            end,
+            &path,
+        );
+        if result.is_err() {
+            println!("Run the same command again to resume the download");
+            process::exit(1);
+        }
+    } else {
+        println!(
+            "Example usage: backfill_candles.exe BTC-USD 3600 2017-12-01 2017-12-31 BTC-USD.csv"
+        );
+    }
+}
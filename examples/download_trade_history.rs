@@ -1,3 +1,4 @@
+extern crate futures;
 extern crate gdax_rs;
 extern crate tokio_core;
 
@@ -5,6 +6,7 @@ extern crate csv;
 extern crate serde;
 
 use std::env;
+use futures::Stream;
 use tokio_core::reactor::Core;
 
 use gdax_rs::{Cursor, Pagination, RESTClient};
@@ -22,33 +24,16 @@ fn download_trade_history(
         &product, startid, &path
     );
     let mut wrt = csv::Writer::from_path(path).unwrap();
-    let mut page = if let Some(lastid) = startid {
-        Some(Pagination {
-            page: Cursor::After(lastid),
-            limit: None,
-        })
-    } else {
-        None
-    };
-
-    loop {
-        if let Ok(trades) =
-            core.run(client.send_request(&GetTrades::new(String::from(product), page)))
-        {
-            if let Some(last) = trades.last() {
-                page = Some(Pagination {
-                    page: Cursor::After(last.trade_id),
-                    limit: None,
-                });
-            } else {
-                break;
-            };
+    let page = startid.map(|lastid| Pagination {
+        page: Cursor::After(lastid),
+        limit: None,
+    });
 
-            for trade in trades {
-                wrt.serialize(trade).unwrap();
-            }
-        }
-    }
+    let trades = client.paginate(&GetTrades::new(String::from(product), page));
+    core.run(trades.for_each(|trade| {
+        wrt.serialize(trade).unwrap();
+        Ok(())
+    })).unwrap();
 
     wrt.flush().unwrap();
 }
@@ -14,5 +14,10 @@ fn main() {
     let currencies = core.run(test_client.send_request(&GetCurrencies::new()))
         .unwrap();
 
-    println!("{:?}", currencies);
+    for currency in currencies {
+        println!(
+            "{} ({}): {}, min size {}, precision {}",
+            currency.id, currency.name, currency.status, currency.min_size, currency.max_precision
+        );
+    }
 }
@@ -6,19 +6,29 @@ use tokio_core::reactor::Core;
 use chrono::{TimeZone, Utc};
 
 use gdax_rs::RESTClient;
-use gdax_rs::products::GetHistoricRates;
+use gdax_rs::products::{GetHistoricRates, Granularity};
 
 fn main() {
     let mut core = Core::new().unwrap();
     let handle = core.handle();
 
     let mut test_client = RESTClient::default(&handle);
-    let candles = core.run(test_client.send_request(&GetHistoricRates::new(
-        String::from("BTC-USD"),
-        Utc.ymd(2017, 12, 21).and_hms_micro(10, 10, 10, 10),
-        Utc.ymd(2017, 12, 21).and_hms_micro(10, 15, 15, 10),
-        60,
-    ))).unwrap();
+    let candles = core.run(test_client.send_request(
+        &GetHistoricRates::new(String::from("BTC-USD"), Granularity::OneMinute).range(
+            Utc.ymd(2017, 12, 21).and_hms_micro(10, 10, 10, 10),
+            Utc.ymd(2017, 12, 21).and_hms_micro(10, 15, 15, 10),
+        ),
+    )).unwrap();
 
     println!("{:?}", candles);
+
+    // a range longer than 300 candles is split in several requests
+    let candles = core.run(test_client.get_candles(
+        "BTC-USD",
+        Utc.ymd(2017, 12, 20).and_hms(0, 0, 0),
+        Utc.ymd(2017, 12, 21).and_hms(0, 0, 0),
+        Granularity::OneMinute,
+    )).unwrap();
+
+    println!("{} candles", candles.len());
 }
@@ -0,0 +1,25 @@
+extern crate gdax_rs;
+extern crate tokio_core;
+
+use std::env;
+use tokio_core::reactor::Core;
+
+use gdax_rs::{Credentials, RESTClient, SANDBOX_API};
+use gdax_rs::accounts::ListAccounts;
+
+fn main() {
+    let mut core = Core::new().unwrap();
+    let handle = core.handle();
+
+    let credentials = Credentials::new(
+        &env::var("GDAX_KEY").expect("GDAX_KEY is not set"),
+        &env::var("GDAX_SECRET").expect("GDAX_SECRET is not set"),
+        &env::var("GDAX_PASSPHRASE").expect("GDAX_PASSPHRASE is not set"),
+    ).unwrap();
+
+    let mut test_client = RESTClient::with_credentials(SANDBOX_API, credentials, &handle).unwrap();
+    let accounts = core.run(test_client.send_request(&ListAccounts::new()))
+        .unwrap();
+
+    println!("{:?}", accounts);
+}
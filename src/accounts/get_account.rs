@@ -0,0 +1,60 @@
+use hyper::Method;
+use uuid::Uuid;
+
+use rest_client::{EndPointRequest, RestRequest};
+use url::Route;
+use super::Account;
+
+/// This struct represents the `Get an Account` end point.
+/// <https://docs.gdax.com/#get-an-account>
+pub struct GetAccount {
+    account_id: Uuid,
+}
+
+impl GetAccount {
+    pub fn new(account_id: Uuid) -> GetAccount {
+        GetAccount { account_id }
+    }
+}
+
+impl EndPointRequest<Account> for GetAccount {
+    fn create_request(&self) -> RestRequest {
+        RestRequest {
+            http_method: Method::Get,
+            route: Route::new()
+                .add_segment(&"accounts")
+                .add_segment(&self.account_id),
+            body: String::new(),
+            pagination: None,
+        }
+    }
+
+    fn requires_authentication(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::Method;
+    use uuid::Uuid;
+
+    use super::{EndPointRequest, GetAccount, RestRequest, Route};
+
+    #[test]
+    fn test_create_request() {
+        let account_id = Uuid::parse_str("a1b2c3d4-0808-4fd7-8914-97829c1925de").unwrap();
+        let result = GetAccount::new(account_id).create_request();
+
+        let expected = RestRequest {
+            http_method: Method::Get,
+            route: Route::new()
+                .add_segment(&"accounts")
+                .add_segment(&"a1b2c3d4-0808-4fd7-8914-97829c1925de"),
+            body: String::new(),
+            pagination: None,
+        };
+
+        assert_eq!(result, expected);
+    }
+}
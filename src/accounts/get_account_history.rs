@@ -0,0 +1,207 @@
+use chrono::{DateTime, Utc};
+use hyper::Method;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use serde_util::{deserialize_from_str, serialize_to_str};
+use rest_client::{EndPointRequest, PaginatedRequest, Pagination, RestRequest};
+use url::Route;
+
+/// This struct represents the `Get Account History` end point, it lists the account activity
+/// (the ledger), the most recent entries come first.
+/// <https://docs.gdax.com/#get-account-history>
+pub struct GetAccountHistory {
+    account_id: Uuid,
+    pagination: Option<Pagination>,
+}
+
+impl GetAccountHistory {
+    pub fn new(account_id: Uuid, pagination: Option<Pagination>) -> GetAccountHistory {
+        GetAccountHistory {
+            account_id,
+            pagination,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct LedgerEntry {
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub id: usize,
+    pub created_at: DateTime<Utc>,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub amount: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub balance: Decimal,
+    /// The `type` and the `details` fields of the entry
+    #[serde(flatten)] pub details: LedgerDetails,
+}
+
+/// The type of a ledger entry, each type comes with its own details.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(tag = "type", content = "details")]
+pub enum LedgerDetails {
+    /// Funds moved to/from Coinbase to GDAX
+    #[serde(rename = "transfer")]
+    Transfer {
+        transfer_id: Uuid,
+        transfer_type: TransferType,
+    },
+    /// Funds moved as a result of a trade
+    #[serde(rename = "match")]
+    Match {
+        order_id: Uuid,
+        #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+        trade_id: usize,
+        product_id: String,
+    },
+    /// Fee as a result of a trade
+    #[serde(rename = "fee")]
+    Fee {
+        order_id: Uuid,
+        #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+        trade_id: usize,
+        product_id: String,
+    },
+    /// Fee rebate as per the fee schedule
+    #[serde(rename = "rebate")]
+    Rebate {
+        order_id: Uuid,
+        #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+        trade_id: usize,
+        product_id: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
+pub enum TransferType {
+    #[serde(rename = "deposit")] Deposit,
+    #[serde(rename = "withdraw")] Withdraw,
+}
+
+impl EndPointRequest<Vec<LedgerEntry>> for GetAccountHistory {
+    fn create_request(&self) -> RestRequest {
+        RestRequest {
+            http_method: Method::Get,
+            route: Route::new()
+                .add_segment(&"accounts")
+                .add_segment(&self.account_id)
+                .add_segment(&"ledger"),
+            body: String::new(),
+            pagination: self.pagination,
+        }
+    }
+
+    fn requires_authentication(&self) -> bool {
+        true
+    }
+}
+
+impl PaginatedRequest<LedgerEntry> for GetAccountHistory {}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use hyper::Method;
+    use serde_json;
+    use uuid::Uuid;
+
+    use rest_client::{Cursor, Pagination};
+    use super::{EndPointRequest, GetAccountHistory, LedgerDetails, LedgerEntry, RestRequest,
+                Route, TransferType};
+
+    #[test]
+    fn test_create_request() {
+        let account_id = Uuid::parse_str("a1b2c3d4-0808-4fd7-8914-97829c1925de").unwrap();
+        let pagination = Some(Pagination {
+            page: Cursor::After(100),
+            limit: Some(10),
+        });
+        let result = GetAccountHistory::new(account_id, pagination).create_request();
+
+        let expected = RestRequest {
+            http_method: Method::Get,
+            route: Route::new()
+                .add_segment(&"accounts")
+                .add_segment(&"a1b2c3d4-0808-4fd7-8914-97829c1925de")
+                .add_segment(&"ledger"),
+            body: String::new(),
+            pagination,
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let result: Vec<LedgerEntry> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "100",
+                    "created_at": "2014-11-07T08:19:27.028459Z",
+                    "amount": "0.001",
+                    "balance": "239.669",
+                    "type": "fee",
+                    "details": {
+                        "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+                        "trade_id": "74",
+                        "product_id": "BTC-USD"
+                    }
+                },
+                {
+                    "id": "99",
+                    "created_at": "2014-11-06T10:34:47.123456Z",
+                    "amount": "100.0",
+                    "balance": "239.668",
+                    "type": "transfer",
+                    "details": {
+                        "transfer_id": "82dcd140-c3c7-4507-8de4-2c529cd1a28f",
+                        "transfer_type": "deposit"
+                    }
+                }
+            ]"#,
+        ).unwrap();
+        let expected = vec![
+            LedgerEntry {
+                id: 100,
+                created_at: Utc.ymd(2014, 11, 7).and_hms_micro(8, 19, 27, 28_459),
+                amount: dec!(0.001),
+                balance: dec!(239.669),
+                details: LedgerDetails::Fee {
+                    order_id: Uuid::parse_str("d50ec984-77a8-460a-b958-66f114b0de9b").unwrap(),
+                    trade_id: 74,
+                    product_id: String::from("BTC-USD"),
+                },
+            },
+            LedgerEntry {
+                id: 99,
+                created_at: Utc.ymd(2014, 11, 6).and_hms_micro(10, 34, 47, 123_456),
+                amount: dec!(100.0),
+                balance: dec!(239.668),
+                details: LedgerDetails::Transfer {
+                    transfer_id: Uuid::parse_str("82dcd140-c3c7-4507-8de4-2c529cd1a28f").unwrap(),
+                    transfer_type: TransferType::Deposit,
+                },
+            },
+        ];
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let json = concat!(
+            r#"[{"id":"100","created_at":"2014-11-07T08:19:27.028459Z","#,
+            r#""amount":"0.001","balance":"239.669","type":"fee","#,
+            r#""details":{"order_id":"d50ec984-77a8-460a-b958-66f114b0de9b","#,
+            r#""trade_id":"74","product_id":"BTC-USD"}},{"id":"101","#,
+            r#""created_at":"2014-11-07T09:19:27.028459Z","amount":"-10.00","#,
+            r#""balance":"229.669","type":"transfer","#,
+            r#""details":{"transfer_id":"1f3b7c4e-5a1d-4c5e-9b0e-2d2f5c6e7a8b","#,
+            r#""transfer_type":"withdraw"}}]"#,
+        );
+        let result: Vec<LedgerEntry> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&result).unwrap(), json);
+    }
+}
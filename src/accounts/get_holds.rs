@@ -0,0 +1,136 @@
+use chrono::{DateTime, Utc};
+use hyper::Method;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use serde_util::{deserialize_from_str, serialize_to_str};
+use rest_client::{EndPointRequest, PaginatedRequest, Pagination, RestRequest};
+use url::Route;
+
+/// This struct represents the `Get Holds` end point, it lists the funds of the account that
+/// are reserved by open orders or pending withdrawals.
+/// <https://docs.gdax.com/#get-holds>
+pub struct GetHolds {
+    account_id: Uuid,
+    pagination: Option<Pagination>,
+}
+
+impl GetHolds {
+    pub fn new(account_id: Uuid, pagination: Option<Pagination>) -> GetHolds {
+        GetHolds {
+            account_id,
+            pagination,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct Hold {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub amount: Decimal,
+    #[serde(rename = "type")] pub hold_type: HoldType,
+    /// The id of the order or of the transfer which created the hold
+    #[serde(rename = "ref")] pub reference: Uuid,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
+pub enum HoldType {
+    #[serde(rename = "order")] Order,
+    #[serde(rename = "transfer")] Transfer,
+}
+
+impl EndPointRequest<Vec<Hold>> for GetHolds {
+    fn create_request(&self) -> RestRequest {
+        RestRequest {
+            http_method: Method::Get,
+            route: Route::new()
+                .add_segment(&"accounts")
+                .add_segment(&self.account_id)
+                .add_segment(&"holds"),
+            body: String::new(),
+            pagination: self.pagination,
+        }
+    }
+
+    fn requires_authentication(&self) -> bool {
+        true
+    }
+}
+
+impl PaginatedRequest<Hold> for GetHolds {}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use hyper::Method;
+    use serde_json;
+    use uuid::Uuid;
+
+    use super::{EndPointRequest, GetHolds, Hold, HoldType, RestRequest, Route};
+
+    #[test]
+    fn test_create_request() {
+        let account_id = Uuid::parse_str("a1b2c3d4-0808-4fd7-8914-97829c1925de").unwrap();
+        let result = GetHolds::new(account_id, None).create_request();
+
+        let expected = RestRequest {
+            http_method: Method::Get,
+            route: Route::new()
+                .add_segment(&"accounts")
+                .add_segment(&"a1b2c3d4-0808-4fd7-8914-97829c1925de")
+                .add_segment(&"holds"),
+            body: String::new(),
+            pagination: None,
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let result: Vec<Hold> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "82dcd140-c3c7-4507-8de4-2c529cd1a28f",
+                    "account_id": "e0b3f39a-183d-453e-b754-0c13e5bab0b3",
+                    "created_at": "2014-11-06T10:34:47.123456Z",
+                    "updated_at": "2014-11-06T10:40:47.123456Z",
+                    "amount": "4.23",
+                    "type": "order",
+                    "ref": "0a205de4-dd35-4370-a285-fe8fc375a273"
+                }
+            ]"#,
+        ).unwrap();
+        let expected = vec![
+            Hold {
+                id: Uuid::parse_str("82dcd140-c3c7-4507-8de4-2c529cd1a28f").unwrap(),
+                account_id: Uuid::parse_str("e0b3f39a-183d-453e-b754-0c13e5bab0b3").unwrap(),
+                created_at: Utc.ymd(2014, 11, 6).and_hms_micro(10, 34, 47, 123_456),
+                updated_at: Utc.ymd(2014, 11, 6).and_hms_micro(10, 40, 47, 123_456),
+                amount: dec!(4.23),
+                hold_type: HoldType::Order,
+                reference: Uuid::parse_str("0a205de4-dd35-4370-a285-fe8fc375a273").unwrap(),
+            },
+        ];
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let json = concat!(
+            r#"[{"id":"82dcd140-c3c7-4507-8de4-2c529cd1a28f","#,
+            r#""account_id":"e0b3f39a-183d-453e-b754-0c13e5bab0b3","#,
+            r#""created_at":"2014-11-06T10:34:47.123456Z","#,
+            r#""updated_at":"2014-11-06T10:40:47.123456Z","amount":"4.23","#,
+            r#""type":"order","ref":"0a205de4-dd35-4370-a285-fe8fc375a273"}]"#,
+        );
+        let result: Vec<Hold> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&result).unwrap(), json);
+    }
+}
@@ -0,0 +1,128 @@
+use hyper::Method;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use serde_util::{deserialize_from_str, serialize_to_str};
+use rest_client::{EndPointRequest, RestRequest};
+use url::Route;
+
+/// This struct represents the `List Accounts` end point.
+/// <https://docs.gdax.com/#list-accounts>
+#[derive(Default)]
+pub struct ListAccounts;
+
+impl ListAccounts {
+    pub fn new() -> ListAccounts {
+        ListAccounts::default()
+    }
+}
+
+/// A trading account, there is one account per currency.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct Account {
+    pub id: Uuid,
+    pub currency: String,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub balance: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub available: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub hold: Decimal,
+    pub profile_id: Uuid,
+}
+
+impl EndPointRequest<Vec<Account>> for ListAccounts {
+    fn create_request(&self) -> RestRequest {
+        RestRequest {
+            http_method: Method::Get,
+            route: Route::new().add_segment(&"accounts"),
+            body: String::new(),
+            pagination: None,
+        }
+    }
+
+    fn requires_authentication(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::Method;
+    use serde_json;
+    use uuid::Uuid;
+
+    use super::{Account, EndPointRequest, ListAccounts, RestRequest, Route};
+
+    #[test]
+    fn test_create_request() {
+        let result = ListAccounts::new().create_request();
+
+        let expected = RestRequest {
+            http_method: Method::Get,
+            route: Route::new().add_segment(&"accounts"),
+            body: String::new(),
+            pagination: None,
+        };
+
+        assert_eq!(result, expected);
+        assert!(ListAccounts::new().requires_authentication());
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let result: Vec<Account> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "71452118-efc7-4cc4-8780-a5e22d4baa53",
+                    "currency": "BTC",
+                    "balance": "0.0000000000000000",
+                    "available": "0.0000000000000000",
+                    "hold": "0.0000000000000000",
+                    "profile_id": "75da88c5-05bf-4f54-bc85-5c775bd68254"
+                },
+                {
+                    "id": "e316cb9a-0808-4fd7-8914-97829c1925de",
+                    "currency": "USD",
+                    "balance": "80.2301373066930000",
+                    "available": "79.2266348066930000",
+                    "hold": "1.0035025000000000",
+                    "profile_id": "75da88c5-05bf-4f54-bc85-5c775bd68254"
+                }
+            ]"#,
+        ).unwrap();
+        let expected = vec![
+            Account {
+                id: Uuid::parse_str("71452118-efc7-4cc4-8780-a5e22d4baa53").unwrap(),
+                currency: String::from("BTC"),
+                balance: dec!(0.0),
+                available: dec!(0.0),
+                hold: dec!(0.0),
+                profile_id: Uuid::parse_str("75da88c5-05bf-4f54-bc85-5c775bd68254").unwrap(),
+            },
+            Account {
+                id: Uuid::parse_str("e316cb9a-0808-4fd7-8914-97829c1925de").unwrap(),
+                currency: String::from("USD"),
+                balance: dec!(80.230137306693),
+                available: dec!(79.226634806693),
+                hold: dec!(1.0035025),
+                profile_id: Uuid::parse_str("75da88c5-05bf-4f54-bc85-5c775bd68254").unwrap(),
+            },
+        ];
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let json = concat!(
+            r#"[{"id":"71452118-efc7-4cc4-8780-a5e22d4baa53","currency":"BTC","#,
+            r#""balance":"80.2301373066930000","available":"79.2266348066930000","#,
+            r#""hold":"1.0035025000000000","#,
+            r#""profile_id":"75da88c5-05bf-4f54-bc85-5c775bd68254"}]"#,
+        );
+        let result: Vec<Account> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&result).unwrap(), json);
+    }
+}
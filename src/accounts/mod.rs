@@ -0,0 +1,14 @@
+//! This module contains all `EndPointRequest` and there response type of GDAX API doc under
+//! "Private/Accounts" section (<https://docs.gdax.com/#accounts>)
+//!
+//! All these end points are private, they require a `RESTClient` created with credentials.
+
+mod list_accounts;
+mod get_account;
+mod get_account_history;
+mod get_holds;
+
+pub use self::list_accounts::{Account, ListAccounts};
+pub use self::get_account::GetAccount;
+pub use self::get_account_history::{GetAccountHistory, LedgerDetails, LedgerEntry, TransferType};
+pub use self::get_holds::{GetHolds, Hold, HoldType};
@@ -0,0 +1,383 @@
+//! This module aggregates trades into candles, GDAX only serves six granularities with
+//! `GetHistoricRates` but the trades of `GetTrades` can be grouped by any interval, or into
+//! bars of the same volume, number of trades or traded value.
+//!
+//! ```ignore
+//! let bars = CandleAggregator::new(BarType::Time(Duration::seconds(10)))
+//!     .empty_intervals(EmptyIntervals::CarryForward)
+//!     .aggregate(&trades);
+//! ```
+
+use std::cell::RefCell;
+use std::cmp::{max, min};
+use std::rc::Rc;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use futures::{future, stream, Future, Stream};
+use rust_decimal::Decimal;
+
+use products::{Candle, Side, Trade};
+
+/// The rule closing a bar.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum BarType {
+    /// One bar per interval, the intervals are aligned on the Unix epoch like the GDAX candles.
+    /// The interval is a whole number of seconds.
+    Time(Duration),
+    /// A bar is closed when its volume reaches the given size in base currency
+    Volume(Decimal),
+    /// A bar is closed after the given number of trades
+    Tick(usize),
+    /// A bar is closed when its traded value reaches the given amount in quote currency
+    Dollar(Decimal),
+}
+
+/// What to do with the intervals without trades of the `BarType::Time` bars.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum EmptyIntervals {
+    /// No bar is created, like GDAX does for its candles
+    Skip,
+    /// A bar without volume is created at the close of the previous bar
+    CarryForward,
+}
+
+/// A candle built from trades, the volume is split by the side of the takers.
+///
+/// The side of a GDAX trade is the side of the maker: a `Side::Sell` trade is a buy of the
+/// taker and counts in `buy_volume`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Bar {
+    /// The start of the interval for the time bars, the time of the first trade otherwise
+    pub candle: Candle,
+    pub buy_volume: Decimal,
+    pub sell_volume: Decimal,
+    /// The traded value in quote currency
+    pub value: Decimal,
+    /// A trade split between two volume or dollar bars counts in both
+    pub trade_count: usize,
+}
+
+impl Bar {
+    fn new(time: DateTime<Utc>, price: Decimal) -> Bar {
+        Bar {
+            candle: Candle {
+                time,
+                low: price,
+                high: price,
+                open: price,
+                close: price,
+                volume: Decimal::ZERO,
+            },
+            buy_volume: Decimal::ZERO,
+            sell_volume: Decimal::ZERO,
+            value: Decimal::ZERO,
+            trade_count: 0,
+        }
+    }
+
+    fn add(&mut self, trade: &Trade, size: Decimal) {
+        self.candle.low = min(self.candle.low, trade.price);
+        self.candle.high = max(self.candle.high, trade.price);
+        self.candle.close = trade.price;
+        self.candle.volume += size;
+        match trade.side {
+            Side::Sell => self.buy_volume += size,
+            Side::Buy => self.sell_volume += size,
+        }
+        self.value += trade.price * size;
+        self.trade_count += 1;
+    }
+}
+
+/// Builds bars from trades given in chronological order.
+pub struct CandleAggregator {
+    bar_type: BarType,
+    empty_intervals: EmptyIntervals,
+    current: Option<Bar>,
+}
+
+impl CandleAggregator {
+    /// Create an aggregator which skips the empty intervals.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interval, size, count or amount of `bar_type` is not positive, or if the
+    /// interval of the time bars has a fraction of a second.
+    pub fn new(bar_type: BarType) -> CandleAggregator {
+        if let BarType::Time(interval) = bar_type {
+            assert!(
+                interval == Duration::seconds(interval.num_seconds()),
+                "The interval of the time bars must be a whole number of seconds: {}",
+                interval
+            );
+        }
+        let positive = match bar_type {
+            BarType::Time(interval) => interval.num_seconds() > 0,
+            BarType::Volume(size) => size > Decimal::ZERO,
+            BarType::Tick(count) => count > 0,
+            BarType::Dollar(amount) => amount > Decimal::ZERO,
+        };
+        assert!(positive, "The bars must have a positive size: {:?}", bar_type);
+
+        CandleAggregator {
+            bar_type,
+            empty_intervals: EmptyIntervals::Skip,
+            current: None,
+        }
+    }
+
+    /// Set how the intervals without trades of the time bars are handled
+    pub fn empty_intervals(mut self, empty_intervals: EmptyIntervals) -> CandleAggregator {
+        self.empty_intervals = empty_intervals;
+        self
+    }
+
+    /// Add a trade and returns the bars that it has closed
+    pub fn push(&mut self, trade: &Trade) -> Vec<Bar> {
+        let mut bars = Vec::new();
+        match self.bar_type {
+            BarType::Time(interval) => {
+                let seconds = interval.num_seconds();
+                let timestamp = trade.time.timestamp();
+                // the remainder is positive for the trades before the epoch too
+                let offset = ((timestamp % seconds) + seconds) % seconds;
+                let start = Utc.timestamp(timestamp - offset, 0);
+
+                let closed = match self.current {
+                    Some(ref bar) => bar.candle.time < start,
+                    None => false,
+                };
+                if closed {
+                    let bar = self.current.take().unwrap();
+                    if self.empty_intervals == EmptyIntervals::CarryForward {
+                        let mut time = bar.candle.time + interval;
+                        let close = bar.candle.close;
+                        bars.push(bar);
+                        while time < start {
+                            bars.push(Bar::new(time, close));
+                            time += interval;
+                        }
+                    } else {
+                        bars.push(bar);
+                    }
+                }
+                self.current
+                    .get_or_insert_with(|| Bar::new(start, trade.price))
+                    .add(trade, trade.size);
+            }
+            BarType::Tick(count) => {
+                let full = {
+                    let bar = self.current
+                        .get_or_insert_with(|| Bar::new(trade.time, trade.price));
+                    bar.add(trade, trade.size);
+                    bar.trade_count >= count
+                };
+                if full {
+                    bars.extend(self.current.take());
+                }
+            }
+            BarType::Volume(_) | BarType::Dollar(_) => {
+                let mut remaining = trade.size;
+                while remaining > Decimal::ZERO {
+                    let capacity = {
+                        let bar = self.current
+                            .get_or_insert_with(|| Bar::new(trade.time, trade.price));
+                        match self.bar_type {
+                            BarType::Dollar(amount) => (amount - bar.value) / trade.price,
+                            BarType::Volume(size) => size - bar.candle.volume,
+                            _ => unreachable!(),
+                        }
+                    };
+                    // the trade is split if it does not fit in the bar
+                    let size = min(remaining, capacity);
+                    self.current.as_mut().unwrap().add(trade, size);
+                    remaining -= size;
+                    if size == capacity {
+                        bars.extend(self.current.take());
+                    }
+                }
+            }
+        }
+        bars
+    }
+
+    /// Returns the bar which is not closed yet, the next trade starts a new bar
+    pub fn flush(&mut self) -> Option<Bar> {
+        self.current.take()
+    }
+
+    /// Aggregate all the trades, they are sorted by trade id so the trades of `GetTrades` can
+    /// be used as is. The last bar is included even if it is not complete.
+    pub fn aggregate(mut self, trades: &[Trade]) -> Vec<Bar> {
+        let mut trades: Vec<&Trade> = trades.iter().collect();
+        trades.sort_by_key(|trade| trade.trade_id);
+
+        let mut bars: Vec<Bar> = trades
+            .into_iter()
+            .flat_map(|trade| self.push(trade))
+            .collect();
+        bars.extend(self.flush());
+        bars
+    }
+
+    /// Aggregate a stream of trades in chronological order, the last bar is sent at the end of
+    /// the stream even if it is not complete.
+    pub fn bars<S>(self, trades: S) -> Box<Stream<Item = Bar, Error = S::Error>>
+    where
+        S: Stream<Item = Trade> + 'static,
+    {
+        let aggregator = Rc::new(RefCell::new(self));
+        let last = Rc::clone(&aggregator);
+
+        let bars = trades
+            .map(move |trade| stream::iter_ok(aggregator.borrow_mut().push(&trade)))
+            .flatten();
+        let rest = future::lazy(move || Ok(stream::iter_ok(last.borrow_mut().flush())))
+            .flatten_stream();
+        Box::new(bars.chain(rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use futures::{stream, Future, Stream};
+    use rust_decimal::Decimal;
+
+    use products::{Candle, Side, Trade};
+    use super::{Bar, BarType, CandleAggregator, EmptyIntervals};
+
+    fn trade(trade_id: usize, second: i64, price: Decimal, size: Decimal, side: Side) -> Trade {
+        Trade {
+            time: Utc.timestamp(1_513_850_400 + second, 0),
+            trade_id,
+            price,
+            size,
+            side,
+        }
+    }
+
+    fn trades() -> Vec<Trade> {
+        vec![
+            trade(1, 1, dec!(100), dec!(1), Side::Sell),
+            trade(2, 4, dec!(102), dec!(0.5), Side::Buy),
+            trade(3, 9, dec!(99), dec!(2), Side::Sell),
+            trade(4, 31, dec!(101), dec!(1.5), Side::Buy),
+        ]
+    }
+
+    /// The candles of the bars as `(time offset, open, high, low, close, volume)`
+    fn summary(bars: &[Bar]) -> Vec<(i64, Decimal, Decimal, Decimal, Decimal, Decimal)> {
+        bars.iter()
+            .map(|bar| {
+                let candle = &bar.candle;
+                (
+                    candle.time.timestamp() - 1_513_850_400,
+                    candle.open,
+                    candle.high,
+                    candle.low,
+                    candle.close,
+                    candle.volume,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_time_bars() {
+        let mut trades = trades();
+        trades.reverse();
+        let bars = CandleAggregator::new(BarType::Time(Duration::seconds(10))).aggregate(&trades);
+
+        assert_eq!(
+            summary(&bars),
+            vec![
+                (0, dec!(100), dec!(102), dec!(99), dec!(99), dec!(3.5)),
+                (30, dec!(101), dec!(101), dec!(101), dec!(101), dec!(1.5)),
+            ]
+        );
+        assert_eq!(bars[0].buy_volume, dec!(3));
+        assert_eq!(bars[0].sell_volume, dec!(0.5));
+        assert_eq!(bars[0].value, dec!(349));
+        assert_eq!(bars[0].trade_count, 3);
+    }
+
+    #[test]
+    fn test_carry_forward() {
+        let bars = CandleAggregator::new(BarType::Time(Duration::seconds(10)))
+            .empty_intervals(EmptyIntervals::CarryForward)
+            .aggregate(&trades());
+
+        assert_eq!(
+            summary(&bars),
+            vec![
+                (0, dec!(100), dec!(102), dec!(99), dec!(99), dec!(3.5)),
+                (10, dec!(99), dec!(99), dec!(99), dec!(99), dec!(0)),
+                (20, dec!(99), dec!(99), dec!(99), dec!(99), dec!(0)),
+                (30, dec!(101), dec!(101), dec!(101), dec!(101), dec!(1.5)),
+            ]
+        );
+        assert_eq!(bars[1].trade_count, 0);
+    }
+
+    #[test]
+    fn test_volume_bars() {
+        let bars = CandleAggregator::new(BarType::Volume(dec!(2))).aggregate(&trades());
+
+        assert_eq!(
+            summary(&bars),
+            vec![
+                (1, dec!(100), dec!(102), dec!(99), dec!(99), dec!(2)),
+                (9, dec!(99), dec!(101), dec!(99), dec!(101), dec!(2)),
+                (31, dec!(101), dec!(101), dec!(101), dec!(101), dec!(1)),
+            ]
+        );
+        assert_eq!(bars[0].buy_volume, dec!(1.5));
+        assert_eq!(bars[1].trade_count, 2);
+    }
+
+    #[test]
+    fn test_tick_and_dollar_bars() {
+        let bars = CandleAggregator::new(BarType::Tick(3)).aggregate(&trades());
+        assert_eq!(
+            summary(&bars),
+            vec![
+                (1, dec!(100), dec!(102), dec!(99), dec!(99), dec!(3.5)),
+                (31, dec!(101), dec!(101), dec!(101), dec!(101), dec!(1.5)),
+            ]
+        );
+
+        let bars = CandleAggregator::new(BarType::Dollar(dec!(198))).aggregate(&trades());
+        let values: Vec<Decimal> = bars.iter().map(|bar| bar.value.round_dp(8)).collect();
+        assert_eq!(values, vec![dec!(198), dec!(198), dec!(104.5)]);
+        assert_eq!(bars[0].candle.volume.round_dp(8), dec!(1.97474747));
+    }
+
+    #[test]
+    #[should_panic(expected = "whole number of seconds")]
+    fn test_sub_second_interval() {
+        CandleAggregator::new(BarType::Time(Duration::milliseconds(1500)));
+    }
+
+    #[test]
+    fn test_stream() {
+        let bars = CandleAggregator::new(BarType::Tick(3))
+            .bars(stream::iter_ok::<_, ()>(trades()))
+            .collect()
+            .wait()
+            .unwrap();
+
+        assert_eq!(bars.len(), 2);
+        assert_eq!(
+            bars[1].candle,
+            Candle {
+                time: Utc.timestamp(1_513_850_431, 0),
+                low: dec!(101),
+                high: dec!(101),
+                open: dec!(101),
+                close: dec!(101),
+                volume: dec!(1.5),
+            }
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use base64;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use hyper::{Method, Request};
+use hyper::header::ContentType;
+use sha2::Sha256;
+
+use error::RestError;
+
+/// The API key, the secret and the passphrase created on GDAX, they are required to access the
+/// private end points (<https://docs.gdax.com/#authentication>).
+#[derive(Clone)]
+pub struct Credentials {
+    key: String,
+    secret: Vec<u8>,
+    passphrase: String,
+}
+
+impl Credentials {
+    /// Create a new `Credentials` object, the secret is given as GDAX displays it (base64 encoded).
+    pub fn new(key: &str, secret: &str, passphrase: &str) -> Result<Credentials, RestError> {
+        let secret = base64::decode(secret)
+            .map_err(|e| RestError::InvalidCredentials(e.to_string()))?;
+        Ok(Credentials {
+            key: String::from(key),
+            secret,
+            passphrase: String::from(passphrase),
+        })
+    }
+
+    pub(crate) fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub(crate) fn passphrase(&self) -> &str {
+        &self.passphrase
+    }
+
+    /// The signature of a request sent at `timestamp` (in seconds since the epoch)
+    pub(crate) fn sign(&self, timestamp: &str, method: &Method, path: &str, body: &str) -> String {
+        sign(&self.secret, timestamp, method, path, body)
+    }
+}
+
+/// Compute the `CB-ACCESS-SIGN` header value: the base64 encoded HMAC-SHA256 of the
+/// timestamp + method + request path (query included) + body string.
+pub fn sign(secret: &[u8], timestamp: &str, method: &Method, path: &str, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_varkey(secret).unwrap(); // hmac accepts keys of any size
+    mac.input(timestamp.as_bytes());
+    mac.input(method.as_ref().as_bytes());
+    mac.input(path.as_bytes());
+    mac.input(body.as_bytes());
+    base64::encode(&mac.result().code())
+}
+
+/// Add the four `CB-ACCESS-*` headers to a request that will be sent to `path` with `body`.
+pub fn authenticate(credentials: &Credentials, request: &mut Request, path: &str, body: &str) {
+    let timestamp = Utc::now().timestamp().to_string();
+    let signature = credentials.sign(&timestamp, request.method(), path, body);
+
+    let headers = request.headers_mut();
+    headers.set_raw("CB-ACCESS-KEY", credentials.key.clone());
+    headers.set_raw("CB-ACCESS-SIGN", signature);
+    headers.set_raw("CB-ACCESS-TIMESTAMP", timestamp);
+    headers.set_raw("CB-ACCESS-PASSPHRASE", credentials.passphrase.clone());
+    headers.set(ContentType::json());
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::Method;
+
+    use super::{sign, Credentials};
+
+    #[test]
+    fn test_sign_get() {
+        let credentials = Credentials::new("key", "Z2RheF9ycyBzZWNyZXQga2V5", "pass").unwrap();
+
+        let result = sign(
+            &credentials.secret,
+            "1514764800",
+            &Method::Get,
+            "/accounts",
+            "",
+        );
+
+        assert_eq!(result, "0Nhem0wpO/ClATpf1pfhmwW0IZynP2bMrWJzJ35W+3M=");
+    }
+
+    #[test]
+    fn test_sign_post() {
+        let credentials = Credentials::new("key", "Z2RheF9ycyBzZWNyZXQga2V5", "pass").unwrap();
+
+        let result = sign(
+            &credentials.secret,
+            "1514764800",
+            &Method::Post,
+            "/orders",
+            "{\"size\":\"1.0\"}",
+        );
+
+        assert_eq!(result, "yRGYHY/NcD9ghZOZio3FZCz84Em2TgWnFeO0V3YrrKI=");
+    }
+
+    #[test]
+    fn test_invalid_secret() {
+        assert!(Credentials::new("key", "not base64!", "pass").is_err());
+    }
+}
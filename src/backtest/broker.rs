@@ -0,0 +1,213 @@
+use std::cmp::{max, min};
+use std::mem;
+
+use chrono::{DateTime, TimeZone, Utc};
+use rust_decimal::Decimal;
+
+use fills::Liquidity;
+use products::Side;
+use simulator::FeeRates;
+use super::report::Execution;
+
+/// The id of an order placed with a `Broker`
+pub type OrderId = usize;
+
+struct PendingOrder {
+    id: OrderId,
+    side: Side,
+    size: Decimal,
+    limit: Option<Decimal>,
+    /// A limit order which crossed the price when it was placed takes the liquidity
+    marketable: bool,
+}
+
+/// Returns `true` if a buy at `limit` can trade at `price`, or a sell if `side` is `Side::Sell`
+fn reaches(side: Side, limit: Decimal, price: Decimal) -> bool {
+    match side {
+        Side::Buy => price <= limit,
+        Side::Sell => price >= limit,
+    }
+}
+
+/// The account of a strategy during a backtest, it holds the cash (in quote currency), the
+/// position (in base currency) and the orders waiting for the next candle or trade.
+///
+/// The market orders are filled at the open of the next candle (or the price of the next trade)
+/// with the slippage, as takers. A limit order rests until a candle reaches its price and it is
+/// then entirely filled at this price as a maker, even when the candle opens beyond it. A limit
+/// order crossing the current price when it is placed is filled like a market order at the next
+/// open, without going beyond its price, if the open still reaches it; otherwise it rests.
+///
+/// The balances are not checked, the cash and the position can become negative.
+pub struct Broker {
+    fees: FeeRates,
+    slippage: Decimal,
+    cash: Decimal,
+    position: Decimal,
+    price: Option<Decimal>,
+    time: DateTime<Utc>,
+    orders: Vec<PendingOrder>,
+    order_count: OrderId,
+    executions: Vec<Execution>,
+}
+
+impl Broker {
+    pub(crate) fn new(cash: Decimal, fees: FeeRates, slippage: Decimal) -> Broker {
+        Broker {
+            fees,
+            slippage,
+            cash,
+            position: Decimal::ZERO,
+            price: None,
+            time: Utc.timestamp(0, 0),
+            orders: Vec::new(),
+            order_count: 0,
+            executions: Vec::new(),
+        }
+    }
+
+    /// Buy `size` at the market price
+    pub fn buy(&mut self, size: Decimal) -> OrderId {
+        self.place(Side::Buy, size, None)
+    }
+
+    /// Sell `size` at the market price
+    pub fn sell(&mut self, size: Decimal) -> OrderId {
+        self.place(Side::Sell, size, None)
+    }
+
+    /// Buy `size` at `price` or lower
+    pub fn buy_limit(&mut self, price: Decimal, size: Decimal) -> OrderId {
+        self.place(Side::Buy, size, Some(price))
+    }
+
+    /// Sell `size` at `price` or higher
+    pub fn sell_limit(&mut self, price: Decimal, size: Decimal) -> OrderId {
+        self.place(Side::Sell, size, Some(price))
+    }
+
+    fn place(&mut self, side: Side, size: Decimal, limit: Option<Decimal>) -> OrderId {
+        let marketable = match (limit, self.price) {
+            (Some(limit), Some(price)) => reaches(side, limit, price),
+            _ => false,
+        };
+        self.order_count += 1;
+        self.orders.push(PendingOrder {
+            id: self.order_count,
+            side,
+            size,
+            limit,
+            marketable,
+        });
+        self.order_count
+    }
+
+    /// Cancel an order which is not filled yet, returns `false` if it is unknown or filled
+    pub fn cancel(&mut self, id: OrderId) -> bool {
+        let count = self.orders.len();
+        self.orders.retain(|order| order.id != id);
+        self.orders.len() < count
+    }
+
+    /// Cancel all the orders which are not filled yet
+    pub fn cancel_all(&mut self) {
+        self.orders.clear();
+    }
+
+    /// Returns `true` if the order is not filled or canceled yet
+    pub fn is_open(&self, id: OrderId) -> bool {
+        self.orders.iter().any(|order| order.id == id)
+    }
+
+    /// The balance in quote currency
+    pub fn cash(&self) -> Decimal {
+        self.cash
+    }
+
+    /// The balance in base currency, it is negative for a short position
+    pub fn position(&self) -> Decimal {
+        self.position
+    }
+
+    /// The close of the current candle or the price of the current trade
+    pub fn price(&self) -> Option<Decimal> {
+        self.price
+    }
+
+    /// The time of the current candle or trade
+    pub fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+
+    /// The value of the account in quote currency at the current price
+    pub fn equity(&self) -> Decimal {
+        self.cash + self.position * self.price.unwrap_or(Decimal::ZERO)
+    }
+
+    /// Fill the pending orders reached by a candle (or a trade with the same price 4 times)
+    pub(crate) fn execute(
+        &mut self,
+        time: DateTime<Utc>,
+        open: Decimal,
+        low: Decimal,
+        high: Decimal,
+    ) {
+        self.time = time;
+        for mut order in mem::replace(&mut self.orders, Vec::new()) {
+            let (market_price, extreme) = match order.side {
+                Side::Buy => (open * (Decimal::ONE + self.slippage), low),
+                Side::Sell => (open * (Decimal::ONE - self.slippage), high),
+            };
+            match order.limit {
+                None => self.fill(&order, market_price, Liquidity::Taker),
+                Some(limit) if order.marketable && reaches(order.side, limit, open) => {
+                    let price = match order.side {
+                        Side::Buy => min(market_price, limit),
+                        Side::Sell => max(market_price, limit),
+                    };
+                    self.fill(&order, price, Liquidity::Taker);
+                }
+                Some(limit) if reaches(order.side, limit, extreme) => {
+                    self.fill(&order, limit, Liquidity::Maker);
+                }
+                Some(_) => {
+                    order.marketable = false;
+                    self.orders.push(order);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn set_price(&mut self, price: Decimal) {
+        self.price = Some(price);
+    }
+
+    pub(crate) fn into_executions(self) -> Vec<Execution> {
+        self.executions
+    }
+
+    fn fill(&mut self, order: &PendingOrder, price: Decimal, liquidity: Liquidity) {
+        let value = price * order.size;
+        let fee = self.fees.fee(price, order.size, liquidity);
+        match order.side {
+            Side::Buy => {
+                self.cash -= value + fee;
+                self.position += order.size;
+            }
+            Side::Sell => {
+                self.cash += value - fee;
+                self.position -= order.size;
+            }
+        }
+
+        self.executions.push(Execution {
+            order_id: order.id,
+            time: self.time,
+            side: order.side,
+            price,
+            size: order.size,
+            fee,
+            liquidity,
+        });
+    }
+}
@@ -0,0 +1,319 @@
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use csv;
+use rust_decimal::Decimal;
+
+use products::{Candle, Trade};
+use simulator::FeeRates;
+use super::broker::Broker;
+use super::report::Report;
+
+/// A trading strategy, it is called once per candle or trade with the `Broker` of the backtest.
+///
+/// The orders placed in a call are filled with the following candles or trades, so a strategy
+/// cannot trade at a price it has already seen.
+pub trait Strategy {
+    /// Called at the close of each candle
+    fn on_candle(&mut self, _candle: &Candle, _broker: &mut Broker) {}
+
+    /// Called after each trade
+    fn on_trade(&mut self, _trade: &Trade, _broker: &mut Broker) {}
+}
+
+/// The settings of a backtest: the initial cash, the fees and the slippage.
+///
+/// The fills of the strategy pay the default `FeeRates` of the simulator unless `fee_rates` is
+/// called, and they have no slippage unless `slippage` is called.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Backtest {
+    initial_cash: Decimal,
+    fees: FeeRates,
+    slippage: Decimal,
+}
+
+impl Backtest {
+    /// Create a backtest starting with `initial_cash` in quote currency and no position
+    pub fn new(initial_cash: Decimal) -> Backtest {
+        Backtest {
+            initial_cash,
+            fees: FeeRates::default(),
+            slippage: Decimal::ZERO,
+        }
+    }
+
+    /// Charge `maker_fee` to the limit orders filled at their price after resting, and
+    /// `taker_fee` to the market orders and the marketable limit orders.
+    pub fn fee_rates(mut self, maker_fee: Decimal, taker_fee: Decimal) -> Backtest {
+        self.fees = FeeRates::new(maker_fee, taker_fee);
+        self
+    }
+
+    /// Set the part of the price lost by the market orders, `0.001` buys 0.1% above the
+    /// market price and sells 0.1% below it.
+    pub fn slippage(mut self, slippage: Decimal) -> Backtest {
+        self.slippage = slippage;
+        self
+    }
+
+    fn broker(&self) -> Broker {
+        Broker::new(self.initial_cash, self.fees, self.slippage)
+    }
+
+    /// Run a strategy on candles, they are sorted by time so the response of `GetHistoricRates`
+    /// can be used as is.
+    pub fn run_candles<S: Strategy>(&self, candles: &[Candle], strategy: &mut S) -> Report {
+        let mut candles: Vec<&Candle> = candles.iter().collect();
+        candles.sort_by_key(|candle| candle.time);
+
+        let mut broker = self.broker();
+        let mut equity_curve = Vec::with_capacity(candles.len());
+        for candle in candles {
+            broker.execute(candle.time, candle.open, candle.low, candle.high);
+            broker.set_price(candle.close);
+            strategy.on_candle(candle, &mut broker);
+            equity_curve.push((candle.time, broker.equity()));
+        }
+        self.report(broker, equity_curve)
+    }
+
+    /// Run a strategy on trades, they are sorted by trade id so the trades of `GetTrades` can be
+    /// used as is.
+    pub fn run_trades<S: Strategy>(&self, trades: &[Trade], strategy: &mut S) -> Report {
+        let mut trades: Vec<&Trade> = trades.iter().collect();
+        trades.sort_by_key(|trade| trade.trade_id);
+
+        let mut broker = self.broker();
+        let mut equity_curve = Vec::with_capacity(trades.len());
+        for trade in trades {
+            broker.execute(trade.time, trade.price, trade.price, trade.price);
+            broker.set_price(trade.price);
+            strategy.on_trade(trade, &mut broker);
+            equity_curve.push((trade.time, broker.equity()));
+        }
+        self.report(broker, equity_curve)
+    }
+
+    fn report(&self, broker: Broker, equity_curve: Vec<(DateTime<Utc>, Decimal)>) -> Report {
+        Report {
+            initial_equity: self.initial_cash,
+            equity_curve,
+            executions: broker.into_executions(),
+        }
+    }
+}
+
+/// Read the trades written by the `download_trade_history` example, a CSV file with headers.
+pub fn read_trades<R: io::Read>(reader: R) -> Result<Vec<Trade>, csv::Error> {
+    let mut trades = csv::Reader::from_reader(reader)
+        .deserialize()
+        .collect::<Result<Vec<Trade>, csv::Error>>()?;
+    trades.sort_by_key(|trade| trade.trade_id);
+    Ok(trades)
+}
+
+/// Load the trades saved in `path` by the `download_trade_history` example
+pub fn load_trades<P: AsRef<Path>>(path: P) -> Result<Vec<Trade>, csv::Error> {
+    read_trades(File::open(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use rust_decimal::Decimal;
+
+    use fills::Liquidity;
+    use products::{Candle, Side, Trade};
+    use super::{read_trades, Backtest, Broker, Strategy};
+
+    /// Buys one unit on the first candle or trade and sells it on the third one
+    #[derive(Default)]
+    struct BuyAndSell {
+        count: usize,
+    }
+
+    impl BuyAndSell {
+        fn next(&mut self, broker: &mut Broker) {
+            self.count += 1;
+            match self.count {
+                1 => {
+                    broker.buy(dec!(1));
+                }
+                3 => {
+                    broker.sell(dec!(1));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    impl Strategy for BuyAndSell {
+        fn on_candle(&mut self, _candle: &Candle, broker: &mut Broker) {
+            self.next(broker);
+        }
+
+        fn on_trade(&mut self, _trade: &Trade, broker: &mut Broker) {
+            self.next(broker);
+        }
+    }
+
+    fn candle(hour: i64, open: Decimal, low: Decimal, high: Decimal, close: Decimal) -> Candle {
+        Candle {
+            time: Utc.timestamp(3600 * hour, 0),
+            low,
+            high,
+            open,
+            close,
+            volume: dec!(10),
+        }
+    }
+
+    #[test]
+    fn test_run_candles() {
+        // newest first, as returned by GDAX
+        let candles = vec![
+            candle(3, dec!(120), dec!(115), dec!(125), dec!(118)),
+            candle(2, dec!(110), dec!(105), dec!(122), dec!(120)),
+            candle(1, dec!(100), dec!(98), dec!(112), dec!(110)),
+            candle(0, dec!(95), dec!(90), dec!(101), dec!(100)),
+        ];
+        let backtest = Backtest::new(dec!(1000))
+            .fee_rates(dec!(0), dec!(0.01))
+            .slippage(dec!(0.001));
+        let report = backtest.run_candles(&candles, &mut BuyAndSell::default());
+
+        let executions: Vec<_> = report
+            .executions
+            .iter()
+            .map(|execution| (execution.side, execution.price, execution.fee))
+            .collect();
+        assert_eq!(
+            executions,
+            vec![
+                (Side::Buy, dec!(100.1), dec!(1.001)),
+                (Side::Sell, dec!(119.88), dec!(1.1988)),
+            ]
+        );
+        assert_eq!(
+            report.equity_curve,
+            vec![
+                (Utc.timestamp(0, 0), dec!(1000)),
+                (Utc.timestamp(3600, 0), dec!(1008.899)),
+                (Utc.timestamp(7200, 0), dec!(1018.899)),
+                (Utc.timestamp(10800, 0), dec!(1017.5802)),
+            ]
+        );
+        assert_eq!(report.final_equity(), dec!(1017.5802));
+    }
+
+    #[test]
+    fn test_limit_orders() {
+        struct Limits;
+
+        impl Strategy for Limits {
+            fn on_candle(&mut self, candle: &Candle, broker: &mut Broker) {
+                if candle.time == Utc.timestamp(0, 0) {
+                    broker.buy_limit(dec!(97), dec!(2));
+                    let id = broker.sell_limit(dec!(130), dec!(1));
+                    assert!(broker.cancel(id));
+                    assert!(!broker.is_open(id));
+                }
+            }
+        }
+
+        let candles = vec![
+            candle(0, dec!(95), dec!(90), dec!(101), dec!(100)),
+            candle(1, dec!(100), dec!(98), dec!(140), dec!(110)),
+            candle(2, dec!(99), dec!(96), dec!(100), dec!(98)),
+        ];
+        let report = Backtest::new(dec!(1000))
+            .fee_rates(dec!(0.001), dec!(0.01))
+            .run_candles(&candles, &mut Limits);
+
+        assert_eq!(report.executions.len(), 1);
+        let execution = &report.executions[0];
+        assert_eq!(execution.time, Utc.timestamp(7200, 0));
+        assert_eq!(execution.price, dec!(97));
+        assert_eq!(execution.fee, dec!(0.194));
+        assert_eq!(execution.liquidity, Liquidity::Maker);
+        assert_eq!(report.final_equity(), dec!(1001.806));
+    }
+
+    #[test]
+    fn test_gap_open() {
+        struct Limits;
+
+        impl Strategy for Limits {
+            fn on_candle(&mut self, candle: &Candle, broker: &mut Broker) {
+                if candle.time == Utc.timestamp(0, 0) {
+                    // below the close: the order rests
+                    broker.buy_limit(dec!(97), dec!(1));
+                    // above the close: the order takes the liquidity
+                    broker.buy_limit(dec!(101), dec!(1));
+                    // below the close, but the next candle opens below the limit so the order rests
+                    broker.sell_limit(dec!(99), dec!(1));
+                }
+            }
+        }
+
+        // the second candle opens at 95, below the price of the resting buy order
+        let candles = vec![
+            candle(0, dec!(98), dec!(97), dec!(101), dec!(100)),
+            candle(1, dec!(95), dec!(94), dec!(99), dec!(96)),
+        ];
+        let report = Backtest::new(dec!(1000))
+            .fee_rates(dec!(0.001), dec!(0.01))
+            .slippage(dec!(0.001))
+            .run_candles(&candles, &mut Limits);
+
+        let executions: Vec<_> = report
+            .executions
+            .iter()
+            .map(|execution| {
+                (
+                    execution.order_id,
+                    execution.price,
+                    execution.fee,
+                    execution.liquidity,
+                )
+            })
+            .collect();
+        assert_eq!(
+            executions,
+            vec![
+                (1, dec!(97), dec!(0.097), Liquidity::Maker),
+                (2, dec!(95.095), dec!(0.95095), Liquidity::Taker),
+                (3, dec!(99), dec!(0.099), Liquidity::Maker),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_trades() {
+        let trades = read_trades(
+            concat!(
+                "time,trade_id,price,size,side\n",
+                "2017-12-21T10:00:03Z,4,101.5,0.1,sell\n",
+                "2017-12-21T10:00:02Z,3,102,0.5,buy\n",
+                "2017-12-21T10:00:01Z,2,100.5,1,sell\n",
+                "2017-12-21T10:00:00Z,1,100,0.2,buy\n",
+            ).as_bytes(),
+        ).unwrap();
+        assert_eq!(trades[0].trade_id, 1);
+        assert_eq!(trades[3].price, dec!(101.5));
+
+        let report = Backtest::new(dec!(1000))
+            .fee_rates(dec!(0), dec!(0))
+            .run_trades(&trades, &mut BuyAndSell::default());
+
+        let prices: Vec<Decimal> = report
+            .executions
+            .iter()
+            .map(|execution| execution.price)
+            .collect();
+        assert_eq!(prices, vec![dec!(100.5), dec!(101.5)]);
+        assert_eq!(report.total_return(), dec!(0.001));
+    }
+}
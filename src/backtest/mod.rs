@@ -0,0 +1,16 @@
+//! This module contains a backtesting engine to evaluate a trading strategy on historical data:
+//! the candles of `GetHistoricRates` or the trades saved by the `download_trade_history`
+//! example.
+//!
+//! A `Strategy` receives each candle or trade in chronological order and places its orders
+//! with the `Broker`. The orders are filled on the following candles or trades with the fees
+//! and the slippage of the `Backtest`, and the run returns a `Report` with the equity curve,
+//! the executions and the usual performance measures.
+
+mod broker;
+mod engine;
+mod report;
+
+pub use self::broker::{Broker, OrderId};
+pub use self::engine::{load_trades, read_trades, Backtest, Strategy};
+pub use self::report::{Execution, Report};
@@ -0,0 +1,167 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use fills::Liquidity;
+use products::Side;
+use super::broker::OrderId;
+
+/// An order filled during a backtest.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Execution {
+    pub order_id: OrderId,
+    pub time: DateTime<Utc>,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+    /// The fee in quote currency
+    pub fee: Decimal,
+    pub liquidity: Liquidity,
+}
+
+/// The result of a backtest, the equity is the value of the account in quote currency.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Report {
+    pub initial_equity: Decimal,
+    /// The equity at the close of each candle or at the price of each trade
+    pub equity_curve: Vec<(DateTime<Utc>, Decimal)>,
+    /// The filled orders in chronological order
+    pub executions: Vec<Execution>,
+}
+
+impl Report {
+    /// The equity at the end of the backtest
+    pub fn final_equity(&self) -> Decimal {
+        self.equity_curve
+            .last()
+            .map_or(self.initial_equity, |&(_, equity)| equity)
+    }
+
+    /// The return over the whole backtest, `0.1` is a gain of 10%
+    pub fn total_return(&self) -> Decimal {
+        self.final_equity() / self.initial_equity - Decimal::ONE
+    }
+
+    /// The largest loss from a peak of equity, `0.1` is a loss of 10% of the peak
+    pub fn max_drawdown(&self) -> Decimal {
+        let mut peak = self.initial_equity;
+        let mut drawdown = Decimal::ZERO;
+        for &(_, equity) in &self.equity_curve {
+            if equity > peak {
+                peak = equity;
+            } else if peak > Decimal::ZERO && (peak - equity) / peak > drawdown {
+                drawdown = (peak - equity) / peak;
+            }
+        }
+        drawdown
+    }
+
+    /// The Sharpe ratio of the returns between two points of the equity curve (with a risk free
+    /// rate of 0), annualized with the number of candles or trades in a year: `8760` for one
+    /// hour candles. Returns `None` if the returns have no variance.
+    pub fn sharpe_ratio(&self, periods_per_year: f64) -> Option<f64> {
+        let equities: Vec<f64> = self.equity_curve
+            .iter()
+            .filter_map(|&(_, equity)| equity.to_f64())
+            .collect();
+        let returns: Vec<f64> = equities
+            .windows(2)
+            .filter(|pair| pair[0] != 0.0)
+            .map(|pair| pair[1] / pair[0] - 1.0)
+            .collect();
+        if returns.len() < 2 {
+            return None;
+        }
+
+        let count = returns.len() as f64;
+        let mean = returns.iter().sum::<f64>() / count;
+        let variance = returns
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>() / (count - 1.0);
+        if variance == 0.0 {
+            return None;
+        }
+        Some(mean / variance.sqrt() * periods_per_year.sqrt())
+    }
+
+    /// The traded value divided by the initial equity
+    pub fn turnover(&self) -> Decimal {
+        let traded = self.executions
+            .iter()
+            .fold(Decimal::ZERO, |total, execution| {
+                total + execution.price * execution.size
+            });
+        traded / self.initial_equity
+    }
+
+    /// The sum of the fees paid in quote currency
+    pub fn total_fees(&self) -> Decimal {
+        self.executions
+            .iter()
+            .fold(Decimal::ZERO, |total, execution| total + execution.fee)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use rust_decimal::Decimal;
+
+    use fills::Liquidity;
+    use products::Side;
+    use super::{Execution, Report};
+
+    fn report(equities: &[Decimal]) -> Report {
+        Report {
+            initial_equity: dec!(100),
+            equity_curve: equities
+                .iter()
+                .enumerate()
+                .map(|(index, equity)| (Utc.timestamp(3600 * index as i64, 0), *equity))
+                .collect(),
+            executions: vec![
+                Execution {
+                    order_id: 1,
+                    time: Utc.timestamp(0, 0),
+                    side: Side::Buy,
+                    price: dec!(10),
+                    size: dec!(5),
+                    fee: dec!(0.125),
+                    liquidity: Liquidity::Taker,
+                },
+                Execution {
+                    order_id: 2,
+                    time: Utc.timestamp(3600, 0),
+                    side: Side::Sell,
+                    price: dec!(12),
+                    size: dec!(5),
+                    fee: dec!(0),
+                    liquidity: Liquidity::Maker,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_measures() {
+        let report = report(&[dec!(110), dec!(120), dec!(90), dec!(105), dec!(125)]);
+
+        assert_eq!(report.final_equity(), dec!(125));
+        assert_eq!(report.total_return(), dec!(0.25));
+        assert_eq!(report.max_drawdown(), dec!(0.25));
+        assert_eq!(report.turnover(), dec!(1.1));
+        assert_eq!(report.total_fees(), dec!(0.125));
+
+        let sharpe = report.sharpe_ratio(1.0).unwrap();
+        assert!((sharpe - 0.2425).abs() < 0.0001, "sharpe ratio {}", sharpe);
+    }
+
+    #[test]
+    fn test_without_variance() {
+        let report = report(&[dec!(100), dec!(100), dec!(100)]);
+
+        assert_eq!(report.max_drawdown(), dec!(0));
+        assert_eq!(report.sharpe_ratio(8760.0), None);
+    }
+}
@@ -2,8 +2,9 @@
 //! "Market Data/Currencies" section (<https://docs.gdax.com/#currencies>)
 
 use hyper::Method;
+use rust_decimal::Decimal;
 
-use serde_util::deserialize_from_str;
+use serde_util::{deserialize_from_str, serialize_to_str};
 use rest_client::{EndPointRequest, RestRequest};
 use url::Route;
 
@@ -16,11 +17,39 @@ impl GetCurrencies {
     }
 }
 
+/// A currency supported by GDAX, for trading or only for deposits and withdrawals.
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 pub struct Currency {
-    id: String,
-    name: String,
-    #[serde(deserialize_with = "deserialize_from_str")] min_size: f64,
+    pub id: String,
+    pub name: String,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub min_size: Decimal,
+    /// `online` or `offline`
+    pub status: String,
+    /// Explains why the currency is offline
+    #[serde(default)] pub message: Option<String>,
+    /// The smallest unit of the currency
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub max_precision: Decimal,
+    #[serde(default)] pub convertible_to: Vec<String>,
+    pub details: CurrencyDetails,
+}
+
+/// The description of a currency, the crypto currencies have more details than the fiat ones.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct CurrencyDetails {
+    /// `crypto` or `fiat`
+    #[serde(rename = "type")] pub currency_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")] pub symbol: Option<String>,
+    /// The number of confirmations needed before a deposit is credited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_confirmations: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")] pub sort_order: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crypto_address_link: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crypto_transaction_link: Option<String>,
+    #[serde(default)] pub push_payment_methods: Vec<String>,
 }
 
 impl EndPointRequest<Vec<Currency>> for GetCurrencies {
@@ -39,7 +68,7 @@ mod tests {
     use hyper::Method;
     use serde_json;
 
-    use super::{Currency, EndPointRequest, GetCurrencies, RestRequest, Route};
+    use super::{Currency, CurrencyDetails, EndPointRequest, GetCurrencies, RestRequest, Route};
 
     #[test]
     fn test_create_request() {
@@ -58,29 +87,92 @@ mod tests {
     #[test]
     fn test_deserialize() {
         let result: Vec<Currency> = serde_json::from_str(
-            "[{
-    \"id\": \"BTC\",
-    \"name\": \"Bitcoin\",
-    \"min_size\": \"0.00000001\"
-}, {
-    \"id\": \"USD\",
-    \"name\": \"United States Dollar\",
-    \"min_size\": \"0.01000000\"
-}]",
+            r#"[{
+                "id": "BTC",
+                "name": "Bitcoin",
+                "min_size": "0.00000001",
+                "status": "online",
+                "message": null,
+                "max_precision": "0.00000001",
+                "convertible_to": [],
+                "details": {
+                    "type": "crypto",
+                    "symbol": "₿",
+                    "network_confirmations": 3,
+                    "sort_order": 3,
+                    "crypto_address_link": "https://live.blockcypher.com/btc/address/{{address}}",
+                    "crypto_transaction_link": "https://live.blockcypher.com/btc/tx/{{txId}}",
+                    "push_payment_methods": ["crypto"]
+                }
+            }, {
+                "id": "USD",
+                "name": "United States Dollar",
+                "min_size": "0.01000000",
+                "status": "online",
+                "max_precision": "0.01",
+                "convertible_to": ["USDC"],
+                "details": {
+                    "type": "fiat",
+                    "push_payment_methods": ["bank_wire", "swift"]
+                }
+            }]"#,
         ).unwrap();
         let expected = vec![
             Currency {
                 id: String::from("BTC"),
                 name: String::from("Bitcoin"),
-                min_size: 0.00000001,
+                min_size: dec!(0.00000001),
+                status: String::from("online"),
+                message: None,
+                max_precision: dec!(0.00000001),
+                convertible_to: vec![],
+                details: CurrencyDetails {
+                    currency_type: String::from("crypto"),
+                    symbol: Some(String::from("₿")),
+                    network_confirmations: Some(3),
+                    sort_order: Some(3),
+                    crypto_address_link: Some(String::from(
+                        "https://live.blockcypher.com/btc/address/{{address}}",
+                    )),
+                    crypto_transaction_link: Some(String::from(
+                        "https://live.blockcypher.com/btc/tx/{{txId}}",
+                    )),
+                    push_payment_methods: vec![String::from("crypto")],
+                },
             },
             Currency {
                 id: String::from("USD"),
                 name: String::from("United States Dollar"),
-                min_size: 0.01,
+                min_size: dec!(0.01),
+                status: String::from("online"),
+                message: None,
+                max_precision: dec!(0.01),
+                convertible_to: vec![String::from("USDC")],
+                details: CurrencyDetails {
+                    currency_type: String::from("fiat"),
+                    symbol: None,
+                    network_confirmations: None,
+                    sort_order: None,
+                    crypto_address_link: None,
+                    crypto_transaction_link: None,
+                    push_payment_methods: vec![String::from("bank_wire"), String::from("swift")],
+                },
             },
         ];
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let json = concat!(
+            r#"[{"id":"USD","name":"United States Dollar","min_size":"0.01000000","#,
+            r#""status":"online","message":null,"max_precision":"0.01","#,
+            r#""convertible_to":["USDC"],"#,
+            r#""details":{"type":"fiat","push_payment_methods":["bank_wire"]}}]"#,
+        );
+        let result: Vec<Currency> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&result).unwrap(), json);
+    }
 }
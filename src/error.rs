@@ -4,6 +4,9 @@ use std::fmt;
 use std::string::FromUtf8Error;
 
 use hyper;
+use hyper::StatusCode;
+use serde_json;
+use ws;
 
 #[derive(Debug)]
 pub enum RestError {
@@ -21,6 +24,16 @@ pub enum RestError {
     RequestError(String),
     /// This error can happen when the http body response is converted to string
     FromUtf8Error(FromUtf8Error),
+    /// The credentials given to the client are not valid (the secret must be base64 encoded)
+    InvalidCredentials(String),
+    /// The end point is private but the client has no credentials, it contains the route
+    AuthenticationRequired(String),
+    /// GDAX has answered with an error status
+    ApiError(ApiError),
+    /// The websocket feed connection has failed
+    WebSocketError(String),
+    /// The session file cannot be read or written, or it has no response for a request
+    SessionError(String),
 }
 
 #[derive(Debug)]
@@ -36,6 +49,91 @@ impl ParseError {
             error_message,
         }
     }
+
+    /// The body of the GDAX response that could not be parsed
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// The reason why the body could not be parsed
+    pub fn error_message(&self) -> &str {
+        &self.error_message
+    }
+}
+
+/// The error returned by GDAX when the http status is not a success, see
+/// <https://docs.gdax.com/#errors>.
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+    route: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorMessage {
+    message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, message: String, route: String) -> ApiError {
+        ApiError {
+            status,
+            message,
+            route,
+        }
+    }
+
+    /// Create the error from the body of a GDAX response (`{"message": "..."}`), the raw body is
+    /// used as message if it has another format.
+    pub fn from_body(status: StatusCode, body: &[u8], route: String) -> ApiError {
+        let message = match serde_json::from_slice::<ErrorMessage>(body) {
+            Ok(error_message) => error_message.message,
+            Err(_) => String::from_utf8_lossy(body).into_owned(),
+        };
+        ApiError::new(status, message, route)
+    }
+
+    /// The http status of the response
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// The message explaining the error
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The route of the request which has failed
+    pub fn route(&self) -> &str {
+        &self.route
+    }
+
+    /// The client has sent too many requests
+    pub fn is_rate_limited(&self) -> bool {
+        self.status == StatusCode::TooManyRequests
+    }
+
+    /// The credentials are missing, invalid or do not have the permission for this end point
+    pub fn is_unauthorized(&self) -> bool {
+        self.status == StatusCode::Unauthorized || self.status == StatusCode::Forbidden
+    }
+
+    /// The resource (order, account, product...) does not exist
+    pub fn is_not_found(&self) -> bool {
+        self.status == StatusCode::NotFound
+    }
+
+    /// The account balance is too low for the order
+    pub fn is_insufficient_funds(&self) -> bool {
+        self.status == StatusCode::BadRequest
+            && self.message.to_lowercase().contains("insufficient funds")
+    }
+
+    /// GDAX has failed to handle the request
+    pub fn is_server_error(&self) -> bool {
+        self.status.is_server_error()
+    }
 }
 
 impl Error for RestError {
@@ -49,6 +147,13 @@ impl Error for RestError {
             RestError::UriError(_) => "Error while creating the uri",
             RestError::RequestError(_) => "Error while sending the https request to GDAX",
             RestError::FromUtf8Error(_) => "Error while converting GDAX http response to UFT8",
+            RestError::InvalidCredentials(_) => "The GDAX credentials are not valid",
+            RestError::AuthenticationRequired(_) => {
+                "This end point requires a client created with credentials"
+            }
+            RestError::ApiError(_) => "GDAX has answered with an error",
+            RestError::WebSocketError(_) => "Error on the GDAX websocket feed connection",
+            RestError::SessionError(_) => "Error while recording or replaying a session",
         }
     }
 
@@ -72,12 +177,24 @@ impl fmt::Display for RestError {
             ),
             RestError::UriError(ref error_string)
             | RestError::HttpsConnectorError(ref error_string)
-            | RestError::RequestError(ref error_string) => {
+            | RestError::RequestError(ref error_string)
+            | RestError::InvalidCredentials(ref error_string)
+            | RestError::AuthenticationRequired(ref error_string)
+            | RestError::WebSocketError(ref error_string)
+            | RestError::SessionError(ref error_string) => {
                 write!(f, "{} : {}", self.description(), error_string)
             }
             RestError::FromUtf8Error(ref utf8_error) => {
                 write!(f, "{} : {}", self.description(), utf8_error)
             }
+            RestError::ApiError(ref api_error) => write!(
+                f,
+                "{} ({} on {}) : {}",
+                self.description(),
+                api_error.status,
+                api_error.route,
+                api_error.message
+            ),
         }
     }
 }
@@ -105,3 +222,43 @@ impl From<io::Error> for RestError {
         RestError::CoreError(io_error)
     }
 }
+
+impl From<ws::WebSocketError> for RestError {
+    fn from(error: ws::WebSocketError) -> RestError {
+        RestError::WebSocketError(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::StatusCode;
+
+    use super::ApiError;
+
+    #[test]
+    fn test_api_error_from_body() {
+        let error = ApiError::from_body(
+            StatusCode::BadRequest,
+            b"{\"message\": \"Insufficient funds\"}",
+            String::from("/orders"),
+        );
+
+        assert_eq!(error.message(), "Insufficient funds");
+        assert_eq!(error.route(), "/orders");
+        assert!(error.is_insufficient_funds());
+        assert!(!error.is_rate_limited());
+    }
+
+    #[test]
+    fn test_api_error_from_unexpected_body() {
+        let error = ApiError::from_body(
+            StatusCode::TooManyRequests,
+            b"Slow down",
+            String::from("/products"),
+        );
+
+        assert_eq!(error.message(), "Slow down");
+        assert!(error.is_rate_limited());
+        assert!(!error.is_not_found());
+    }
+}
@@ -0,0 +1,189 @@
+//! This module contains all `EndPointRequest` and there response type of GDAX API doc under
+//! "Private/Fills" section (<https://docs.gdax.com/#fills>)
+
+use chrono::{DateTime, Utc};
+use hyper::Method;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use products::Side;
+use serde_util::{deserialize_from_str, serialize_to_str};
+use rest_client::{EndPointRequest, PaginatedRequest, Pagination, RestRequest};
+use url::Route;
+
+/// This struct represents the `List Fills` end point, the fills are filtered either by order or
+/// by product.
+/// <https://docs.gdax.com/#list-fills>
+pub struct GetFills {
+    filter: FillsFilter,
+    pagination: Option<Pagination>,
+}
+
+enum FillsFilter {
+    OrderId(Uuid),
+    ProductId(String),
+}
+
+impl GetFills {
+    /// List the fills of the order `order_id`
+    pub fn for_order(order_id: Uuid, pagination: Option<Pagination>) -> GetFills {
+        GetFills {
+            filter: FillsFilter::OrderId(order_id),
+            pagination,
+        }
+    }
+
+    /// List the fills of all the orders on `product_id`
+    pub fn for_product(product_id: String, pagination: Option<Pagination>) -> GetFills {
+        GetFills {
+            filter: FillsFilter::ProductId(product_id),
+            pagination,
+        }
+    }
+}
+
+/// Indicates if the fill was the result of a liquidity provider or liquidity taker.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
+pub enum Liquidity {
+    #[serde(rename = "M")] Maker,
+    #[serde(rename = "T")] Taker,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct Fill {
+    pub trade_id: usize,
+    pub product_id: String,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub price: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub size: Decimal,
+    pub order_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub liquidity: Liquidity,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub fee: Decimal,
+    pub settled: bool,
+    pub side: Side,
+}
+
+impl EndPointRequest<Vec<Fill>> for GetFills {
+    fn create_request(&self) -> RestRequest {
+        let route = Route::new().add_segment(&"fills");
+        let route = match self.filter {
+            FillsFilter::OrderId(ref order_id) => route.add_attribute_value(&"order_id", order_id),
+            FillsFilter::ProductId(ref product_id) => {
+                route.add_attribute_value(&"product_id", product_id)
+            }
+        };
+
+        RestRequest {
+            http_method: Method::Get,
+            route,
+            body: String::new(),
+            pagination: self.pagination,
+        }
+    }
+
+    fn requires_authentication(&self) -> bool {
+        true
+    }
+}
+
+impl PaginatedRequest<Fill> for GetFills {}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use hyper::Method;
+    use serde_json;
+    use uuid::Uuid;
+
+    use rest_client::{Cursor, Pagination};
+    use super::{EndPointRequest, Fill, GetFills, Liquidity, RestRequest, Route, Side};
+
+    #[test]
+    fn test_create_request_for_order() {
+        let order_id = Uuid::parse_str("d50ec984-77a8-460a-b958-66f114b0de9b").unwrap();
+        let result = GetFills::for_order(order_id, None).create_request();
+
+        let expected = RestRequest {
+            http_method: Method::Get,
+            route: Route::new()
+                .add_segment(&"fills")
+                .add_attribute_value(&"order_id", &"d50ec984-77a8-460a-b958-66f114b0de9b"),
+            body: String::new(),
+            pagination: None,
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_request_for_product() {
+        let pagination = Some(Pagination {
+            page: Cursor::Before(42),
+            limit: Some(100),
+        });
+        let result = GetFills::for_product(String::from("BTC-USD"), pagination).create_request();
+
+        let expected = RestRequest {
+            http_method: Method::Get,
+            route: Route::new()
+                .add_segment(&"fills")
+                .add_attribute_value(&"product_id", &"BTC-USD"),
+            body: String::new(),
+            pagination,
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let result: Vec<Fill> = serde_json::from_str(
+            r#"[
+                {
+                    "trade_id": 74,
+                    "product_id": "BTC-USD",
+                    "price": "10.00",
+                    "size": "0.01",
+                    "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+                    "created_at": "2014-11-07T22:19:28.578544Z",
+                    "liquidity": "T",
+                    "fee": "0.00025",
+                    "settled": true,
+                    "side": "buy"
+                }
+            ]"#,
+        ).unwrap();
+        let expected = vec![
+            Fill {
+                trade_id: 74,
+                product_id: String::from("BTC-USD"),
+                price: dec!(10.0),
+                size: dec!(0.01),
+                order_id: Uuid::parse_str("d50ec984-77a8-460a-b958-66f114b0de9b").unwrap(),
+                created_at: Utc.ymd(2014, 11, 7).and_hms_micro(22, 19, 28, 578_544),
+                liquidity: Liquidity::Taker,
+                fee: dec!(0.00025),
+                settled: true,
+                side: Side::Buy,
+            },
+        ];
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let json = concat!(
+            r#"[{"trade_id":74,"product_id":"BTC-USD","price":"10.00","size":"0.01","#,
+            r#""order_id":"d50ec984-77a8-460a-b958-66f114b0de9b","#,
+            r#""created_at":"2014-11-07T22:19:28.578544Z","liquidity":"T","#,
+            r#""fee":"0.00025","settled":true,"side":"buy"}]"#,
+        );
+        let result: Vec<Fill> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&result).unwrap(), json);
+    }
+}
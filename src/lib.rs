@@ -1,6 +1,7 @@
 //! This crate is a wrapper around GDAX API ([https://docs.gdax.com](https://docs.gdax.com/)).
 //!
-//! At this stage it support **only the public end points**.
+//! The public end points can be used without an account, the private ones require a client
+//! created with [`RESTClient::with_credentials`](struct.RESTClient.html#method.with_credentials).
 //!
 //! # How it works
 //!
@@ -14,26 +15,56 @@
 //!
 //! # Examples
 
+extern crate base64;
 extern crate chrono;
+extern crate csv;
 extern crate futures;
 // #[macro_use]
+extern crate hmac;
 extern crate hyper;
 extern crate hyper_tls;
 extern crate itertools;
 extern crate mockito;
 extern crate percent_encoding;
+extern crate rand;
+extern crate rust_decimal;
+#[cfg(test)]
+#[macro_use]
+extern crate rust_decimal_macros;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 extern crate serde_json;
+extern crate sha2;
 extern crate tokio_core;
 extern crate uuid;
+extern crate websocket as ws;
 
+pub mod accounts;
+pub mod orders;
+pub mod fills;
 pub mod products;
 pub mod currencies;
 pub mod time;
+pub mod error;
+pub mod websocket;
+pub mod order_book;
+pub mod simulator;
+pub mod backtest;
+pub mod aggregation;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 mod url;
 mod rest_client;
-mod error;
 mod serde_util;
-pub use rest_client::{Cursor, Pagination, RESTClient};
+mod auth;
+mod rate_limiter;
+mod retry;
+mod session;
+pub use rest_client::{Cursor, Page, PaginatedRequest, Pagination, RESTClient, PUBLIC_API,
+                      SANDBOX_API};
+pub use auth::Credentials;
+pub use rate_limiter::RateLimiter;
+pub use retry::RetryPolicy;
+pub use session::Session;
+pub use rust_decimal::Decimal;
@@ -0,0 +1,258 @@
+use std::cmp::min;
+
+use rust_decimal::Decimal;
+
+use products::{OrderBook, PriceLevel, Side};
+
+/// The number of basis points in 1
+fn basis_points() -> Decimal {
+    Decimal::new(10_000, 0)
+}
+
+/// The amount of a market order
+enum Amount {
+    Size(Decimal),
+    Funds(Decimal),
+}
+
+/// The expected execution of a market order walking through the book.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct FillEstimate {
+    /// The size filled in base currency
+    pub size: Decimal,
+    /// The value filled in quote currency
+    pub funds: Decimal,
+    pub average_price: Decimal,
+    /// The distance between the average price and the mid price in basis points, it includes
+    /// half of the spread
+    pub slippage_bps: Decimal,
+    /// `false` if the book has not enough liquidity to fill the whole order
+    pub complete: bool,
+}
+
+/// The analytics of a `GetProductOrderBook` response (or of `LocalOrderBook::depth` and
+/// `FullOrderBook::to_order_book`), the bids must be sorted from the highest price and the
+/// asks from the lowest price like GDAX does.
+impl OrderBook<PriceLevel> {
+    /// The highest bid
+    pub fn best_bid(&self) -> Option<&PriceLevel> {
+        self.bids.first()
+    }
+
+    /// The lowest ask
+    pub fn best_ask(&self) -> Option<&PriceLevel> {
+        self.asks.first()
+    }
+
+    /// The difference between the best ask and the best bid
+    pub fn spread(&self) -> Option<Decimal> {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) => Some(ask.price - bid.price),
+            _ => None,
+        }
+    }
+
+    /// The spread in basis points of the mid price
+    pub fn spread_bps(&self) -> Option<Decimal> {
+        match (self.spread(), self.mid_price()) {
+            (Some(spread), Some(mid)) if !mid.is_zero() => Some(spread / mid * basis_points()),
+            _ => None,
+        }
+    }
+
+    /// The average of the best bid and the best ask
+    pub fn mid_price(&self) -> Option<Decimal> {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) => Some((bid.price + ask.price) / Decimal::new(2, 0)),
+            _ => None,
+        }
+    }
+
+    /// The best bid and the best ask weighted by the size of the opposite side, the price moves
+    /// towards the side which has the smallest size.
+    pub fn microprice(&self) -> Option<Decimal> {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) if !(bid.size + ask.size).is_zero() => Some(
+                (bid.price * ask.size + ask.price * bid.size) / (bid.size + ask.size),
+            ),
+            _ => None,
+        }
+    }
+
+    /// The cumulative size of the bids (`Side::Buy`) or of the asks (`Side::Sell`) within `bps`
+    /// basis points of the mid price
+    pub fn depth_within(&self, side: Side, bps: Decimal) -> Decimal {
+        let mid = match self.mid_price() {
+            Some(mid) => mid,
+            None => return Decimal::ZERO,
+        };
+        let distance = mid * bps / basis_points();
+        let levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        levels
+            .iter()
+            .take_while(|level| match side {
+                Side::Buy => level.price >= mid - distance,
+                Side::Sell => level.price <= mid + distance,
+            })
+            .fold(Decimal::ZERO, |total, level| total + level.size)
+    }
+
+    /// The difference between the bid and the ask depths within `bps` basis points of the mid
+    /// price, divided by their sum: from -1 when there are only asks to 1 when there are only
+    /// bids.
+    pub fn imbalance(&self, bps: Decimal) -> Option<Decimal> {
+        let bids = self.depth_within(Side::Buy, bps);
+        let asks = self.depth_within(Side::Sell, bps);
+        if (bids + asks).is_zero() {
+            None
+        } else {
+            Some((bids - asks) / (bids + asks))
+        }
+    }
+
+    /// Estimate the execution of a market order of `size` in base currency
+    pub fn estimate_size(&self, side: Side, size: Decimal) -> Option<FillEstimate> {
+        self.estimate(side, Amount::Size(size))
+    }
+
+    /// Estimate the execution of a market order of `funds` in quote currency
+    pub fn estimate_funds(&self, side: Side, funds: Decimal) -> Option<FillEstimate> {
+        self.estimate(side, Amount::Funds(funds))
+    }
+
+    /// Walk through the opposite side of the book until the amount is filled
+    fn estimate(&self, side: Side, amount: Amount) -> Option<FillEstimate> {
+        let mid = self.mid_price();
+        let levels = match side {
+            Side::Buy => &self.asks,
+            Side::Sell => &self.bids,
+        };
+
+        let mut size = Decimal::ZERO;
+        let mut funds = Decimal::ZERO;
+        let mut complete = false;
+        for level in levels {
+            let wanted = match amount {
+                Amount::Size(total) => total - size,
+                Amount::Funds(total) => (total - funds) / level.price,
+            };
+            let taken = min(level.size, wanted);
+            size += taken;
+            funds += taken * level.price;
+            if taken == wanted {
+                complete = true;
+                break;
+            }
+        }
+        if size <= Decimal::ZERO {
+            return None;
+        }
+
+        let average_price = funds / size;
+        let slippage_bps = match mid {
+            Some(mid) if !mid.is_zero() => (average_price - mid).abs() / mid * basis_points(),
+            _ => Decimal::ZERO,
+        };
+        Some(FillEstimate {
+            size,
+            funds,
+            average_price,
+            slippage_bps,
+            complete,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+
+    use products::{OrderBook, OrderInfo, PriceLevel, Side};
+    use super::FillEstimate;
+
+    fn level(price: Decimal, size: Decimal) -> PriceLevel {
+        PriceLevel {
+            price,
+            size,
+            orderinfo: OrderInfo::Count(1),
+        }
+    }
+
+    fn order_book() -> OrderBook<PriceLevel> {
+        OrderBook {
+            sequence: 1,
+            bids: vec![
+                level(dec!(99), dec!(1)),
+                level(dec!(98), dec!(2)),
+                level(dec!(90), dec!(5)),
+            ],
+            asks: vec![level(dec!(101), dec!(3)), level(dec!(102), dec!(1))],
+        }
+    }
+
+    #[test]
+    fn test_prices() {
+        let book = order_book();
+
+        assert_eq!(book.best_bid().map(|bid| bid.price), Some(dec!(99)));
+        assert_eq!(book.best_ask().map(|ask| ask.price), Some(dec!(101)));
+        assert_eq!(book.spread(), Some(dec!(2)));
+        assert_eq!(book.mid_price(), Some(dec!(100)));
+        assert_eq!(book.spread_bps(), Some(dec!(200)));
+        assert_eq!(book.microprice(), Some(dec!(99.5)));
+
+        let empty = OrderBook {
+            sequence: 1,
+            bids: vec![level(dec!(99), dec!(1))],
+            asks: vec![],
+        };
+        assert_eq!(empty.spread(), None);
+        assert_eq!(empty.microprice(), None);
+        assert_eq!(empty.estimate_size(Side::Buy, dec!(1)), None);
+    }
+
+    #[test]
+    fn test_depth() {
+        let book = order_book();
+
+        assert_eq!(book.depth_within(Side::Buy, dec!(100)), dec!(1));
+        assert_eq!(book.depth_within(Side::Buy, dec!(200)), dec!(3));
+        assert_eq!(book.depth_within(Side::Sell, dec!(200)), dec!(4));
+        assert_eq!(book.imbalance(dec!(100)), Some(dec!(-0.5)));
+        assert_eq!(book.imbalance(dec!(1)), None);
+    }
+
+    #[test]
+    fn test_estimate() {
+        let book = order_book();
+
+        assert_eq!(
+            book.estimate_size(Side::Buy, dec!(3.5)),
+            Some(FillEstimate {
+                size: dec!(3.5),
+                funds: dec!(354),
+                average_price: dec!(354) / dec!(3.5),
+                slippage_bps: (dec!(354) / dec!(3.5) - dec!(100)) * dec!(100),
+                complete: true,
+            })
+        );
+        assert_eq!(
+            book.estimate_funds(Side::Sell, dec!(197)),
+            Some(FillEstimate {
+                size: dec!(2),
+                funds: dec!(197),
+                average_price: dec!(98.5),
+                slippage_bps: dec!(150),
+                complete: true,
+            })
+        );
+
+        let estimate = book.estimate_size(Side::Buy, dec!(10)).unwrap();
+        assert_eq!(estimate.size, dec!(4));
+        assert_eq!(estimate.funds, dec!(405));
+        assert!(!estimate.complete);
+    }
+}
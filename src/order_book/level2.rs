@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+
+use rust_decimal::Decimal;
+
+use products::{OrderBook, OrderInfo, PriceLevel, Side};
+use websocket::{L2Change, L2Update, Snapshot};
+
+/// An aggregated (level 2) order book kept up to date with the `level2` channel of the
+/// websocket feed.
+///
+/// The book is seeded with a `GetProductOrderBook` response of `Level::Top50` or with the
+/// `Snapshot` message of the feed, then each `L2Update` of the product must be applied in the
+/// order of reception. The feed does not send the number of orders of the price levels, the
+/// levels created or modified by an update have an `OrderInfo::Count(0)`.
+#[derive(Debug, Clone)]
+pub struct LocalOrderBook {
+    bids: BTreeMap<Decimal, PriceLevel>,
+    asks: BTreeMap<Decimal, PriceLevel>,
+}
+
+impl LocalOrderBook {
+    /// Create a book from the response of `GetProductOrderBook`
+    pub fn from_order_book(order_book: OrderBook<PriceLevel>) -> LocalOrderBook {
+        let to_map = |levels: Vec<PriceLevel>| {
+            levels
+                .into_iter()
+                .map(|level| (level.price, level))
+                .collect()
+        };
+
+        LocalOrderBook {
+            bids: to_map(order_book.bids),
+            asks: to_map(order_book.asks),
+        }
+    }
+
+    /// Create a book from the first message of the `level2` channel
+    pub fn from_snapshot(snapshot: &Snapshot) -> LocalOrderBook {
+        let mut book = LocalOrderBook {
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+        };
+        for level in &snapshot.bids {
+            book.set_level(Side::Buy, level.price, level.size);
+        }
+        for level in &snapshot.asks {
+            book.set_level(Side::Sell, level.price, level.size);
+        }
+        book
+    }
+
+    /// Apply all the changes of an update
+    pub fn apply_update(&mut self, update: &L2Update) {
+        for change in &update.changes {
+            self.apply_change(change);
+        }
+    }
+
+    /// Set the new size of a price level, the level is removed if the size is 0
+    pub fn apply_change(&mut self, change: &L2Change) {
+        self.set_level(change.side, change.price, change.size);
+    }
+
+    fn set_level(&mut self, side: Side, price: Decimal, size: Decimal) {
+        let levels = match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        };
+
+        if size.is_zero() {
+            levels.remove(&price);
+        } else {
+            levels.insert(
+                price,
+                PriceLevel {
+                    price,
+                    size,
+                    orderinfo: OrderInfo::Count(0),
+                },
+            );
+        }
+    }
+
+    /// The highest bid
+    pub fn best_bid(&self) -> Option<&PriceLevel> {
+        self.bids.values().next_back()
+    }
+
+    /// The lowest ask
+    pub fn best_ask(&self) -> Option<&PriceLevel> {
+        self.asks.values().next()
+    }
+
+    /// The difference between the best ask and the best bid
+    pub fn spread(&self) -> Option<Decimal> {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) => Some(ask.price - bid.price),
+            _ => None,
+        }
+    }
+
+    /// The bids sorted from the highest price to the lowest
+    pub fn bids<'a>(&'a self) -> Box<Iterator<Item = &'a PriceLevel> + 'a> {
+        Box::new(self.bids.values().rev())
+    }
+
+    /// The asks sorted from the lowest price to the highest
+    pub fn asks<'a>(&'a self) -> Box<Iterator<Item = &'a PriceLevel> + 'a> {
+        Box::new(self.asks.values())
+    }
+
+    /// The `levels` best bids and asks in the same format as the `GetProductOrderBook`
+    /// response, the sequence is always 0 because the level 2 feed has no sequence.
+    pub fn depth(&self, levels: usize) -> OrderBook<PriceLevel> {
+        OrderBook {
+            sequence: 0,
+            bids: self.bids().take(levels).cloned().collect(),
+            asks: self.asks().take(levels).cloned().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+    use products::{OrderBook, OrderInfo, PriceLevel, Side};
+    use websocket::{L2Change, L2Update, Snapshot, SnapshotLevel};
+    use super::LocalOrderBook;
+
+    fn level(price: Decimal, size: Decimal, count: i64) -> PriceLevel {
+        PriceLevel {
+            price,
+            size,
+            orderinfo: OrderInfo::Count(count),
+        }
+    }
+
+    fn snapshot_book() -> LocalOrderBook {
+        LocalOrderBook::from_snapshot(&Snapshot {
+            product_id: String::from("BTC-USD"),
+            bids: vec![
+                SnapshotLevel {
+                    price: dec!(100.0),
+                    size: dec!(1.0),
+                },
+                SnapshotLevel {
+                    price: dec!(99.5),
+                    size: dec!(2.0),
+                },
+            ],
+            asks: vec![
+                SnapshotLevel {
+                    price: dec!(100.5),
+                    size: dec!(3.0),
+                },
+                SnapshotLevel {
+                    price: dec!(101.0),
+                    size: dec!(4.0),
+                },
+            ],
+        })
+    }
+
+    #[test]
+    fn test_from_order_book() {
+        let book = LocalOrderBook::from_order_book(OrderBook {
+            sequence: 3,
+            bids: vec![level(dec!(99.0), dec!(1.0), 2), level(dec!(98.0), dec!(2.0), 1)],
+            asks: vec![level(dec!(101.0), dec!(0.5), 4), level(dec!(102.0), dec!(3.0), 1)],
+        });
+
+        assert_eq!(book.best_bid(), Some(&level(dec!(99.0), dec!(1.0), 2)));
+        assert_eq!(book.best_ask(), Some(&level(dec!(101.0), dec!(0.5), 4)));
+        assert_eq!(book.spread(), Some(dec!(2.0)));
+    }
+
+    #[test]
+    fn test_from_snapshot() {
+        let book = snapshot_book();
+
+        let bids: Vec<Decimal> = book.bids().map(|level| level.price).collect();
+        let asks: Vec<Decimal> = book.asks().map(|level| level.price).collect();
+        assert_eq!(bids, vec![dec!(100.0), dec!(99.5)]);
+        assert_eq!(asks, vec![dec!(100.5), dec!(101.0)]);
+    }
+
+    #[test]
+    fn test_apply_update() {
+        let mut book = snapshot_book();
+
+        book.apply_update(&L2Update {
+            product_id: String::from("BTC-USD"),
+            time: None,
+            changes: vec![
+                L2Change {
+                    side: Side::Buy,
+                    price: dec!(100.0),
+                    size: dec!(0.0),
+                },
+                L2Change {
+                    side: Side::Buy,
+                    price: dec!(99.75),
+                    size: dec!(5.0),
+                },
+                L2Change {
+                    side: Side::Sell,
+                    price: dec!(101.0),
+                    size: dec!(1.5),
+                },
+            ],
+        });
+
+        assert_eq!(book.best_bid(), Some(&level(dec!(99.75), dec!(5.0), 0)));
+        assert_eq!(book.best_ask(), Some(&level(dec!(100.5), dec!(3.0), 0)));
+        assert_eq!(
+            book.depth(1),
+            OrderBook {
+                sequence: 0,
+                bids: vec![level(dec!(99.75), dec!(5.0), 0)],
+                asks: vec![level(dec!(100.5), dec!(3.0), 0)],
+            }
+        );
+        assert_eq!(
+            book.depth(5).asks,
+            vec![level(dec!(100.5), dec!(3.0), 0), level(dec!(101.0), dec!(1.5), 0)]
+        );
+    }
+
+    #[test]
+    fn test_empty_side() {
+        let mut book = snapshot_book();
+
+        book.apply_change(&L2Change {
+            side: Side::Sell,
+            price: dec!(100.5),
+            size: dec!(0.0),
+        });
+        book.apply_change(&L2Change {
+            side: Side::Sell,
+            price: dec!(101.0),
+            size: dec!(0.0),
+        });
+
+        assert_eq!(book.best_ask(), None);
+        assert_eq!(book.spread(), None);
+        assert_eq!(book.depth(10).asks, vec![]);
+    }
+}
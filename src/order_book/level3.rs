@@ -0,0 +1,510 @@
+use std::collections::{BTreeMap, HashMap};
+
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use products::{GetProductOrderBook, Level, OrderBook, OrderInfo, PriceLevel, Side};
+use websocket::Message;
+
+/// The default number of messages that a book buffers while it waits for a snapshot
+pub const MAX_BUFFERED_MESSAGES: usize = 100_000;
+
+/// A message of the `full` channel has been missed, the book has been cleared and it waits for
+/// a new snapshot.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct SequenceGap {
+    pub expected: usize,
+    pub received: usize,
+}
+
+/// The book is out of sync, a new snapshot must be requested with `snapshot_request` and given
+/// to `seed`.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum SyncError {
+    SequenceGap(SequenceGap),
+    /// The buffer has reached its limit while waiting for a snapshot, the buffered messages
+    /// have been dropped
+    BufferFull,
+}
+
+/// A full (level 3) order book of a product kept up to date with the `full` channel of the
+/// websocket feed (<https://docs.gdax.com/#real-time-order-book>).
+///
+/// The book must be fed with all the messages of the feed from the subscription, the messages
+/// are buffered until the book is seeded with the `GetProductOrderBook` response returned by
+/// `snapshot_request`. When a sequence gap is detected the book is cleared and it buffers the
+/// messages again until it receives a new snapshot. The buffer is dropped when it exceeds its
+/// limit, so a snapshot which never comes does not hold all the messages of the feed.
+///
+/// Each order of the book is a `PriceLevel` with an `OrderInfo::Id`, the orders of a price are
+/// kept in their arrival order.
+#[derive(Debug, Clone)]
+pub struct FullOrderBook {
+    product_id: String,
+    /// The sequence of the last applied message, `None` while the book waits for a snapshot
+    sequence: Option<usize>,
+    buffer: Vec<Message>,
+    buffer_limit: usize,
+    bids: BTreeMap<Decimal, Vec<PriceLevel>>,
+    asks: BTreeMap<Decimal, Vec<PriceLevel>>,
+    orders: HashMap<Uuid, (Side, Decimal)>,
+}
+
+impl FullOrderBook {
+    /// Create an empty book waiting for its snapshot
+    pub fn new(product_id: String) -> FullOrderBook {
+        FullOrderBook {
+            product_id,
+            sequence: None,
+            buffer: Vec::new(),
+            buffer_limit: MAX_BUFFERED_MESSAGES,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            orders: HashMap::new(),
+        }
+    }
+
+    /// Set the number of messages buffered while the book waits for a snapshot
+    pub fn buffer_limit(mut self, buffer_limit: usize) -> FullOrderBook {
+        self.buffer_limit = buffer_limit;
+        self
+    }
+
+    /// The request of the snapshot to give to `seed`, it must be sent after the subscription
+    /// to the `full` channel.
+    pub fn snapshot_request(&self) -> GetProductOrderBook {
+        GetProductOrderBook::new(self.product_id.clone(), Level::Full)
+    }
+
+    /// `true` if the book has been seeded and no message has been missed since
+    pub fn is_synced(&self) -> bool {
+        self.sequence.is_some()
+    }
+
+    /// The sequence of the last message applied to the book
+    pub fn sequence(&self) -> Option<usize> {
+        self.sequence
+    }
+
+    /// Replace the content of the book by a level 3 snapshot and apply the buffered messages
+    /// that are more recent than the snapshot. The levels without an order id are ignored.
+    ///
+    /// It fails if the snapshot is older than the first buffered message, a new snapshot is
+    /// required in that case.
+    pub fn seed(&mut self, order_book: OrderBook<PriceLevel>) -> Result<(), SyncError> {
+        self.clear();
+        for order in order_book.bids {
+            self.open_order(Side::Buy, order);
+        }
+        for order in order_book.asks {
+            self.open_order(Side::Sell, order);
+        }
+        self.sequence = Some(order_book.sequence);
+
+        let buffer: Vec<Message> = self.buffer.drain(..).collect();
+        let mut result = Ok(());
+        for message in buffer {
+            // after a gap, the messages are buffered again
+            if let Err(gap) = self.process(message) {
+                result = Err(gap);
+            }
+        }
+        result
+    }
+
+    /// Apply a message of the feed, the messages of the other products and channels are
+    /// ignored as well as the messages older than the book.
+    pub fn process(&mut self, message: Message) -> Result<(), SyncError> {
+        let sequence = match self.sequence_of(&message) {
+            Some(sequence) => sequence,
+            None => return Ok(()),
+        };
+
+        let last = match self.sequence {
+            Some(last) => last,
+            None => return self.buffer_message(message),
+        };
+
+        if sequence <= last {
+            Ok(())
+        } else if sequence == last + 1 {
+            self.apply(message);
+            self.sequence = Some(sequence);
+            Ok(())
+        } else {
+            self.clear();
+            self.buffer.push(message);
+            Err(SyncError::SequenceGap(SequenceGap {
+                expected: last + 1,
+                received: sequence,
+            }))
+        }
+    }
+
+    fn buffer_message(&mut self, message: Message) -> Result<(), SyncError> {
+        if self.buffer.len() >= self.buffer_limit {
+            self.buffer.clear();
+            return Err(SyncError::BufferFull);
+        }
+        self.buffer.push(message);
+        Ok(())
+    }
+
+    fn sequence_of(&self, message: &Message) -> Option<usize> {
+        let (product_id, sequence) = match *message {
+            Message::Received(ref received) => (&received.product_id, received.sequence),
+            Message::Open(ref open) => (&open.product_id, open.sequence),
+            Message::Done(ref done) => (&done.product_id, done.sequence),
+            Message::Match(ref match_message) => {
+                (&match_message.product_id, match_message.sequence)
+            }
+            Message::Change(ref change) => (&change.product_id, change.sequence),
+            _ => return None,
+        };
+
+        if *product_id == self.product_id {
+            Some(sequence)
+        } else {
+            None
+        }
+    }
+
+    fn apply(&mut self, message: Message) {
+        match message {
+            Message::Open(open) => self.open_order(open.side, PriceLevel {
+                price: open.price,
+                size: open.remaining_size,
+                orderinfo: OrderInfo::Id(open.order_id),
+            }),
+            Message::Done(done) => self.remove_order(&done.order_id),
+            Message::Match(match_message) => {
+                if let Some(order) = self.order_mut(&match_message.maker_order_id) {
+                    order.size -= match_message.size;
+                }
+            }
+            Message::Change(change) => {
+                if let (Some(order), Some(new_size)) =
+                    (self.order_mut(&change.order_id), change.new_size)
+                {
+                    order.size = new_size;
+                }
+            }
+            // the received orders are not on the book yet
+            _ => {}
+        }
+    }
+
+    fn clear(&mut self) {
+        self.sequence = None;
+        self.bids.clear();
+        self.asks.clear();
+        self.orders.clear();
+    }
+
+    fn side_mut(&mut self, side: Side) -> &mut BTreeMap<Decimal, Vec<PriceLevel>> {
+        match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        }
+    }
+
+    fn open_order(&mut self, side: Side, order: PriceLevel) {
+        let id = match order.orderinfo {
+            OrderInfo::Id(id) => id,
+            OrderInfo::Count(_) => return,
+        };
+        let price = order.price;
+
+        self.orders.insert(id, (side, price));
+        self.side_mut(side)
+            .entry(price)
+            .or_default()
+            .push(order);
+    }
+
+    fn remove_order(&mut self, id: &Uuid) {
+        let (side, price) = match self.orders.remove(id) {
+            Some(location) => location,
+            None => return,
+        };
+
+        let levels = self.side_mut(side);
+        let empty = match levels.get_mut(&price) {
+            Some(orders) => {
+                orders.retain(|order| order.orderinfo != OrderInfo::Id(*id));
+                orders.is_empty()
+            }
+            None => false,
+        };
+        if empty {
+            levels.remove(&price);
+        }
+    }
+
+    fn order_mut(&mut self, id: &Uuid) -> Option<&mut PriceLevel> {
+        let (side, price) = match self.orders.get(id) {
+            Some(&location) => location,
+            None => return None,
+        };
+
+        self.side_mut(side)
+            .get_mut(&price)
+            .and_then(|orders| {
+                orders
+                    .iter_mut()
+                    .find(|order| order.orderinfo == OrderInfo::Id(*id))
+            })
+    }
+
+    /// An order of the book
+    pub fn order(&self, id: &Uuid) -> Option<&PriceLevel> {
+        let &(side, price) = self.orders.get(id)?;
+        let levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+
+        levels
+            .get(&price)
+            .and_then(|orders| orders.iter().find(|order| order.orderinfo == OrderInfo::Id(*id)))
+    }
+
+    /// The highest bid price level, aggregated like the `Level::Best` book
+    pub fn best_bid(&self) -> Option<PriceLevel> {
+        self.bids.values().next_back().map(|orders| aggregate(orders))
+    }
+
+    /// The lowest ask price level, aggregated like the `Level::Best` book
+    pub fn best_ask(&self) -> Option<PriceLevel> {
+        self.asks.values().next().map(|orders| aggregate(orders))
+    }
+
+    /// The buy orders from the highest price to the lowest, in arrival order for a price
+    pub fn bids<'a>(&'a self) -> Box<Iterator<Item = &'a PriceLevel> + 'a> {
+        Box::new(self.bids.values().rev().flat_map(|orders| orders.iter()))
+    }
+
+    /// The sell orders from the lowest price to the highest, in arrival order for a price
+    pub fn asks<'a>(&'a self) -> Box<Iterator<Item = &'a PriceLevel> + 'a> {
+        Box::new(self.asks.values().flat_map(|orders| orders.iter()))
+    }
+
+    /// The book in the same format as the `GetProductOrderBook` response of `Level::Full`, it
+    /// is `None` while the book waits for a snapshot.
+    pub fn to_order_book(&self) -> Option<OrderBook<PriceLevel>> {
+        self.sequence.map(|sequence| OrderBook {
+            sequence,
+            bids: self.bids().cloned().collect(),
+            asks: self.asks().cloned().collect(),
+        })
+    }
+}
+
+fn aggregate(orders: &[PriceLevel]) -> PriceLevel {
+    PriceLevel {
+        price: orders[0].price,
+        size: orders.iter().map(|order| order.size).sum(),
+        orderinfo: OrderInfo::Count(orders.len() as i64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+    use serde_json;
+    use uuid::Uuid;
+
+    use products::{OrderBook, OrderInfo, PriceLevel};
+    use websocket::Message;
+    use super::{FullOrderBook, SequenceGap, SyncError};
+
+    const ORDER_1: &str = "3b0f1225-7f84-490b-a29f-0faef9de823a";
+    const ORDER_2: &str = "da863862-25f4-4868-ac41-005d11ab0a5f";
+    const ORDER_3: &str = "7b52009b-64fd-0a2a-49e6-d8a939753077";
+
+    fn order(price: Decimal, size: Decimal, id: &str) -> PriceLevel {
+        PriceLevel {
+            price,
+            size,
+            orderinfo: OrderInfo::Id(Uuid::parse_str(id).unwrap()),
+        }
+    }
+
+    fn snapshot(sequence: usize) -> OrderBook<PriceLevel> {
+        OrderBook {
+            sequence,
+            bids: vec![order(dec!(100.0), dec!(1.0), ORDER_1)],
+            asks: vec![order(dec!(101.0), dec!(2.0), ORDER_2)],
+        }
+    }
+
+    fn open(sequence: usize, id: &str, price: &str, size: &str, side: &str) -> Message {
+        serde_json::from_str(&format!(
+            "{{\"type\":\"open\",\"time\":\"2014-11-07T08:19:27.028459Z\",\
+             \"product_id\":\"BTC-USD\",\"sequence\":{},\"order_id\":\"{}\",\"price\":\"{}\",\
+             \"remaining_size\":\"{}\",\"side\":\"{}\"}}",
+            sequence, id, price, size, side
+        )).unwrap()
+    }
+
+    fn done(sequence: usize, id: &str, side: &str) -> Message {
+        serde_json::from_str(&format!(
+            "{{\"type\":\"done\",\"time\":\"2014-11-07T08:19:27.028459Z\",\
+             \"product_id\":\"BTC-USD\",\"sequence\":{},\"order_id\":\"{}\",\
+             \"reason\":\"canceled\",\"side\":\"{}\"}}",
+            sequence, id, side
+        )).unwrap()
+    }
+
+    fn match_message(sequence: usize, maker_id: &str, size: &str, side: &str) -> Message {
+        serde_json::from_str(&format!(
+            "{{\"type\":\"match\",\"trade_id\":10,\"sequence\":{},\"maker_order_id\":\"{}\",\
+             \"taker_order_id\":\"132fb6ae-456b-4654-b4e0-d681ac05cea1\",\
+             \"time\":\"2014-11-07T08:19:27.028459Z\",\"product_id\":\"BTC-USD\",\
+             \"size\":\"{}\",\"price\":\"100.0\",\"side\":\"{}\"}}",
+            sequence, maker_id, size, side
+        )).unwrap()
+    }
+
+    #[test]
+    fn test_seed_with_buffered_messages() {
+        let mut book = FullOrderBook::new(String::from("BTC-USD"));
+
+        // these messages are received before the snapshot
+        assert_eq!(book.process(open(10, ORDER_3, "100.0", "3.0", "buy")), Ok(()));
+        assert_eq!(book.process(done(11, ORDER_2, "sell")), Ok(()));
+        assert_eq!(book.process(open(12, ORDER_2, "102.0", "0.5", "sell")), Ok(()));
+        assert!(!book.is_synced());
+        assert_eq!(book.to_order_book(), None);
+
+        // the open message 10 is already in the snapshot
+        assert_eq!(
+            book.seed(OrderBook {
+                sequence: 10,
+                bids: vec![
+                    order(dec!(100.0), dec!(1.0), ORDER_1),
+                    order(dec!(100.0), dec!(3.0), ORDER_3),
+                ],
+                asks: vec![order(dec!(101.0), dec!(2.0), ORDER_2)],
+            }),
+            Ok(())
+        );
+
+        assert!(book.is_synced());
+        assert_eq!(book.sequence(), Some(12));
+        assert_eq!(
+            book.to_order_book(),
+            Some(OrderBook {
+                sequence: 12,
+                bids: vec![
+                    order(dec!(100.0), dec!(1.0), ORDER_1),
+                    order(dec!(100.0), dec!(3.0), ORDER_3),
+                ],
+                asks: vec![order(dec!(102.0), dec!(0.5), ORDER_2)],
+            })
+        );
+        assert_eq!(
+            book.best_bid(),
+            Some(PriceLevel {
+                price: dec!(100.0),
+                size: dec!(4.0),
+                orderinfo: OrderInfo::Count(2),
+            })
+        );
+    }
+
+    #[test]
+    fn test_match_and_change() {
+        let mut book = FullOrderBook::new(String::from("BTC-USD"));
+        book.seed(snapshot(5)).unwrap();
+
+        book.process(match_message(6, ORDER_1, "0.25", "buy")).unwrap();
+        let change = serde_json::from_str(&format!(
+            "{{\"type\":\"change\",\"time\":\"2014-11-07T08:19:27.028459Z\",\"sequence\":7,\
+             \"order_id\":\"{}\",\"product_id\":\"BTC-USD\",\"new_size\":\"1.5\",\
+             \"old_size\":\"2.0\",\"price\":\"101.0\",\"side\":\"sell\"}}",
+            ORDER_2
+        )).unwrap();
+        book.process(change).unwrap();
+
+        let order_1 = Uuid::parse_str(ORDER_1).unwrap();
+        let order_2 = Uuid::parse_str(ORDER_2).unwrap();
+        assert_eq!(book.order(&order_1), Some(&order(dec!(100.0), dec!(0.75), ORDER_1)));
+        assert_eq!(book.order(&order_2), Some(&order(dec!(101.0), dec!(1.5), ORDER_2)));
+
+        book.process(done(8, ORDER_1, "buy")).unwrap();
+        assert_eq!(book.order(&order_1), None);
+        assert_eq!(book.best_bid(), None);
+        assert_eq!(book.bids().count(), 0);
+    }
+
+    #[test]
+    fn test_ignore_other_messages() {
+        let mut book = FullOrderBook::new(String::from("ETH-USD"));
+        book.seed(snapshot(5)).unwrap();
+
+        // another product and an old message
+        book.process(open(100, ORDER_3, "1.0", "1.0", "buy")).unwrap();
+        book.process(done(4, ORDER_1, "buy")).unwrap();
+
+        assert_eq!(book.sequence(), Some(5));
+        assert_eq!(book.to_order_book(), Some(snapshot(5)));
+    }
+
+    #[test]
+    fn test_sequence_gap() {
+        let mut book = FullOrderBook::new(String::from("BTC-USD"));
+        book.seed(snapshot(5)).unwrap();
+
+        assert_eq!(
+            book.process(done(7, ORDER_1, "buy")),
+            Err(SyncError::SequenceGap(SequenceGap {
+                expected: 6,
+                received: 7,
+            }))
+        );
+        assert!(!book.is_synced());
+        assert_eq!(book.asks().count(), 0);
+
+        // the book resynchronises with a new snapshot and the buffered message
+        book.seed(snapshot(6)).unwrap();
+        assert_eq!(book.sequence(), Some(7));
+        assert_eq!(book.bids().count(), 0);
+        assert_eq!(book.asks().count(), 1);
+    }
+
+    #[test]
+    fn test_snapshot_too_old() {
+        let mut book = FullOrderBook::new(String::from("BTC-USD"));
+        book.process(done(10, ORDER_1, "buy")).unwrap();
+
+        assert_eq!(
+            book.seed(snapshot(5)),
+            Err(SyncError::SequenceGap(SequenceGap {
+                expected: 6,
+                received: 10,
+            }))
+        );
+        assert!(!book.is_synced());
+
+        book.seed(snapshot(9)).unwrap();
+        assert_eq!(book.sequence(), Some(10));
+    }
+
+    #[test]
+    fn test_buffer_limit() {
+        let mut book = FullOrderBook::new(String::from("BTC-USD")).buffer_limit(2);
+        book.process(open(10, ORDER_3, "100.0", "3.0", "buy")).unwrap();
+        book.process(done(11, ORDER_3, "buy")).unwrap();
+
+        assert_eq!(
+            book.process(done(12, ORDER_1, "buy")),
+            Err(SyncError::BufferFull)
+        );
+
+        // the dropped messages are not applied after the snapshot
+        book.seed(snapshot(12)).unwrap();
+        assert_eq!(book.sequence(), Some(12));
+        assert_eq!(book.to_order_book(), Some(snapshot(12)));
+    }
+}
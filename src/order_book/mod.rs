@@ -0,0 +1,14 @@
+//! This module contains the order books maintained locally from the websocket feed, they are
+//! seeded with the `GetProductOrderBook` end point or with a feed snapshot and then updated
+//! with each message of the feed.
+//!
+//! It also adds analytics to the `OrderBook<PriceLevel>` responses: spread, mid price,
+//! microprice, depth, imbalance and the estimation of the market orders execution.
+
+mod analytics;
+mod level2;
+mod level3;
+
+pub use self::analytics::FillEstimate;
+pub use self::level2::LocalOrderBook;
+pub use self::level3::{FullOrderBook, SequenceGap, SyncError, MAX_BUFFERED_MESSAGES};
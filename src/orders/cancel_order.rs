@@ -0,0 +1,122 @@
+use hyper::Method;
+use uuid::Uuid;
+
+use rest_client::{EndPointRequest, RestRequest};
+use url::Route;
+
+/// This struct represents the `Cancel an order` end point, GDAX answers with the id of the
+/// canceled order.
+/// <https://docs.gdax.com/#cancel-an-order>
+pub struct CancelOrder {
+    order_id: Uuid,
+}
+
+impl CancelOrder {
+    pub fn new(order_id: Uuid) -> CancelOrder {
+        CancelOrder { order_id }
+    }
+}
+
+impl EndPointRequest<Vec<Uuid>> for CancelOrder {
+    fn create_request(&self) -> RestRequest {
+        RestRequest {
+            http_method: Method::Delete,
+            route: Route::new()
+                .add_segment(&"orders")
+                .add_segment(&self.order_id),
+            body: String::new(),
+            pagination: None,
+        }
+    }
+
+    fn requires_authentication(&self) -> bool {
+        true
+    }
+}
+
+/// This struct represents the `Cancel all` end point, GDAX answers with the ids of the
+/// canceled orders.
+/// <https://docs.gdax.com/#cancel-all>
+#[derive(Default)]
+pub struct CancelAllOrders {
+    product_id: Option<String>,
+}
+
+impl CancelAllOrders {
+    /// Cancel the open orders of all the products
+    pub fn new() -> CancelAllOrders {
+        CancelAllOrders::default()
+    }
+
+    /// Cancel only the open orders of `product_id`
+    pub fn for_product(product_id: String) -> CancelAllOrders {
+        CancelAllOrders {
+            product_id: Some(product_id),
+        }
+    }
+}
+
+impl EndPointRequest<Vec<Uuid>> for CancelAllOrders {
+    fn create_request(&self) -> RestRequest {
+        let mut route = Route::new().add_segment(&"orders");
+        if let Some(ref product_id) = self.product_id {
+            route = route.add_attribute_value(&"product_id", product_id);
+        }
+
+        RestRequest {
+            http_method: Method::Delete,
+            route,
+            body: String::new(),
+            pagination: None,
+        }
+    }
+
+    fn requires_authentication(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::Method;
+    use uuid::Uuid;
+
+    use super::{CancelAllOrders, CancelOrder, EndPointRequest, RestRequest, Route};
+
+    #[test]
+    fn test_create_request() {
+        let order_id = Uuid::parse_str("d0c5340b-6d6c-49d9-b567-48c4bfca13d2").unwrap();
+        let result = CancelOrder::new(order_id).create_request();
+
+        let expected = RestRequest {
+            http_method: Method::Delete,
+            route: Route::new()
+                .add_segment(&"orders")
+                .add_segment(&"d0c5340b-6d6c-49d9-b567-48c4bfca13d2"),
+            body: String::new(),
+            pagination: None,
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_cancel_all_request() {
+        let result = CancelAllOrders::for_product(String::from("BTC-USD")).create_request();
+
+        let expected = RestRequest {
+            http_method: Method::Delete,
+            route: Route::new()
+                .add_segment(&"orders")
+                .add_attribute_value(&"product_id", &"BTC-USD"),
+            body: String::new(),
+            pagination: None,
+        };
+
+        assert_eq!(result, expected);
+        assert_eq!(
+            CancelAllOrders::new().create_request().route,
+            Route::new().add_segment(&"orders")
+        );
+    }
+}
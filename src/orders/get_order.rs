@@ -0,0 +1,234 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use hyper::Method;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use products::Side;
+use serde_util::{deserialize_from_str, deserialize_option_from_str, serialize_option_to_str,
+                 serialize_to_str};
+use rest_client::{EndPointRequest, RestRequest};
+use url::Route;
+use super::{OrderType, SelfTradePrevention, TimeInForce};
+
+/// This struct represents the `Get an order` end point.
+/// <https://docs.gdax.com/#get-an-order>
+pub struct GetOrder {
+    order_id: Uuid,
+}
+
+impl GetOrder {
+    pub fn new(order_id: Uuid) -> GetOrder {
+        GetOrder { order_id }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
+pub enum OrderStatus {
+    #[serde(rename = "open")] Open,
+    #[serde(rename = "pending")] Pending,
+    #[serde(rename = "active")] Active,
+    #[serde(rename = "done")] Done,
+}
+
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let status = match *self {
+            OrderStatus::Open => "open",
+            OrderStatus::Pending => "pending",
+            OrderStatus::Active => "active",
+            OrderStatus::Done => "done",
+        };
+        write!(f, "{}", status)
+    }
+}
+
+/// An order as returned by GDAX, the fields that depend on the order type are optional.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Order {
+    pub id: Uuid,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    #[serde(serialize_with = "serialize_option_to_str")]
+    pub price: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    #[serde(serialize_with = "serialize_option_to_str")]
+    pub size: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    #[serde(serialize_with = "serialize_option_to_str")]
+    pub funds: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    #[serde(serialize_with = "serialize_option_to_str")]
+    pub specified_funds: Option<Decimal>,
+    pub product_id: String,
+    pub side: Side,
+    pub stp: Option<SelfTradePrevention>,
+    #[serde(rename = "type")] pub order_type: OrderType,
+    pub time_in_force: Option<TimeInForce>,
+    #[serde(default)] pub post_only: bool,
+    pub created_at: DateTime<Utc>,
+    pub done_at: Option<DateTime<Utc>>,
+    pub done_reason: Option<String>,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub fill_fees: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub filled_size: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub executed_value: Decimal,
+    pub status: OrderStatus,
+    pub settled: bool,
+}
+
+impl EndPointRequest<Order> for GetOrder {
+    fn create_request(&self) -> RestRequest {
+        RestRequest {
+            http_method: Method::Get,
+            route: Route::new()
+                .add_segment(&"orders")
+                .add_segment(&self.order_id),
+            body: String::new(),
+            pagination: None,
+        }
+    }
+
+    fn requires_authentication(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use hyper::Method;
+    use serde_json;
+    use uuid::Uuid;
+
+    use super::{EndPointRequest, GetOrder, Order, OrderStatus, OrderType, RestRequest, Route,
+                SelfTradePrevention, Side, TimeInForce};
+
+    #[test]
+    fn test_create_request() {
+        let order_id = Uuid::parse_str("d0c5340b-6d6c-49d9-b567-48c4bfca13d2").unwrap();
+        let result = GetOrder::new(order_id).create_request();
+
+        let expected = RestRequest {
+            http_method: Method::Get,
+            route: Route::new()
+                .add_segment(&"orders")
+                .add_segment(&"d0c5340b-6d6c-49d9-b567-48c4bfca13d2"),
+            body: String::new(),
+            pagination: None,
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deserialize_limit() {
+        let result: Order = serde_json::from_str(
+            r#"{
+                "id": "d0c5340b-6d6c-49d9-b567-48c4bfca13d2",
+                "price": "0.10000000",
+                "size": "0.01000000",
+                "product_id": "BTC-USD",
+                "side": "buy",
+                "stp": "dc",
+                "type": "limit",
+                "time_in_force": "GTC",
+                "post_only": false,
+                "created_at": "2016-12-08T20:02:28.53864Z",
+                "fill_fees": "0.0000000000000000",
+                "filled_size": "0.00000000",
+                "executed_value": "0.0000000000000000",
+                "status": "pending",
+                "settled": false
+            }"#,
+        ).unwrap();
+        let expected = Order {
+            id: Uuid::parse_str("d0c5340b-6d6c-49d9-b567-48c4bfca13d2").unwrap(),
+            price: Some(dec!(0.1)),
+            size: Some(dec!(0.01)),
+            funds: None,
+            specified_funds: None,
+            product_id: String::from("BTC-USD"),
+            side: Side::Buy,
+            stp: Some(SelfTradePrevention::DecreaseAndCancel),
+            order_type: OrderType::Limit,
+            time_in_force: Some(TimeInForce::GoodTillCanceled),
+            post_only: false,
+            created_at: Utc.ymd(2016, 12, 8).and_hms_micro(20, 2, 28, 538_640),
+            done_at: None,
+            done_reason: None,
+            fill_fees: dec!(0.0),
+            filled_size: dec!(0.0),
+            executed_value: dec!(0.0),
+            status: OrderStatus::Pending,
+            settled: false,
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deserialize_market() {
+        let result: Order = serde_json::from_str(
+            r#"{
+                "id": "b227e691-365c-470f-a860-a9b4a37dd1d8",
+                "funds": "9.9750623400000000",
+                "specified_funds": "10.0000000000000000",
+                "product_id": "BTC-USD",
+                "side": "buy",
+                "type": "market",
+                "post_only": false,
+                "created_at": "2016-12-08T20:01:19.038644Z",
+                "done_at": "2016-12-08T20:01:19.069Z",
+                "done_reason": "filled",
+                "fill_fees": "0.0249376391550000",
+                "filled_size": "0.01291771",
+                "executed_value": "9.9750556620000000",
+                "status": "done",
+                "settled": true
+            }"#,
+        ).unwrap();
+        let expected = Order {
+            id: Uuid::parse_str("b227e691-365c-470f-a860-a9b4a37dd1d8").unwrap(),
+            price: None,
+            size: None,
+            funds: Some(dec!(9.97506234)),
+            specified_funds: Some(dec!(10.0)),
+            product_id: String::from("BTC-USD"),
+            side: Side::Buy,
+            stp: None,
+            order_type: OrderType::Market,
+            time_in_force: None,
+            post_only: false,
+            created_at: Utc.ymd(2016, 12, 8).and_hms_micro(20, 1, 19, 38_644),
+            done_at: Some(Utc.ymd(2016, 12, 8).and_hms_micro(20, 1, 19, 69_000)),
+            done_reason: Some(String::from("filled")),
+            fill_fees: dec!(0.024937639155),
+            filled_size: dec!(0.01291771),
+            executed_value: dec!(9.975055662),
+            status: OrderStatus::Done,
+            settled: true,
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let json = concat!(
+            r#"{"id":"d0c5340b-6d6c-49d9-b567-48c4bfca13d2","price":"0.10000000","#,
+            r#""size":"0.01000000","funds":null,"specified_funds":null,"#,
+            r#""product_id":"BTC-USD","side":"buy","stp":"dc","type":"limit","#,
+            r#""time_in_force":"GTC","post_only":false,"#,
+            r#""created_at":"2016-12-08T20:02:28.538640Z","done_at":null,"#,
+            r#""done_reason":null,"fill_fees":"0.0000000000000000","#,
+            r#""filled_size":"0.00000000","executed_value":"0.0000000000000000","#,
+            r#""status":"pending","settled":false}"#,
+        );
+        let result: Order = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&result).unwrap(), json);
+    }
+}
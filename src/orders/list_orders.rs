@@ -0,0 +1,84 @@
+use hyper::Method;
+
+use rest_client::{EndPointRequest, PaginatedRequest, Pagination, RestRequest};
+use url::Route;
+use super::{Order, OrderStatus};
+
+/// This struct represents the `List Orders` end point.
+/// <https://docs.gdax.com/#list-orders>
+pub struct ListOrders {
+    status: Vec<OrderStatus>,
+    product_id: Option<String>,
+    pagination: Option<Pagination>,
+}
+
+impl ListOrders {
+    /// List the orders with one of the given `status`, when `status` is empty GDAX returns the
+    /// open, pending and active orders. The orders of all the products are returned if
+    /// `product_id` is `None`.
+    pub fn new(
+        status: Vec<OrderStatus>,
+        product_id: Option<String>,
+        pagination: Option<Pagination>,
+    ) -> ListOrders {
+        ListOrders {
+            status,
+            product_id,
+            pagination,
+        }
+    }
+}
+
+impl EndPointRequest<Vec<Order>> for ListOrders {
+    fn create_request(&self) -> RestRequest {
+        let mut route = Route::new().add_segment(&"orders");
+        for status in &self.status {
+            route = route.add_attribute_value(&"status", status);
+        }
+        if let Some(ref product_id) = self.product_id {
+            route = route.add_attribute_value(&"product_id", product_id);
+        }
+
+        RestRequest {
+            http_method: Method::Get,
+            route,
+            body: String::new(),
+            pagination: self.pagination,
+        }
+    }
+
+    fn requires_authentication(&self) -> bool {
+        true
+    }
+}
+
+impl PaginatedRequest<Order> for ListOrders {}
+
+#[cfg(test)]
+mod tests {
+    use hyper::Method;
+
+    use super::{EndPointRequest, ListOrders, OrderStatus, RestRequest, Route};
+
+    #[test]
+    fn test_create_request() {
+        let result = ListOrders::new(
+            vec![OrderStatus::Open, OrderStatus::Done],
+            Some(String::from("BTC-USD")),
+            None,
+        ).create_request();
+
+        let expected = RestRequest {
+            http_method: Method::Get,
+            route: Route::new()
+                .add_segment(&"orders")
+                .add_attribute_value(&"status", &"open")
+                .add_attribute_value(&"status", &"done")
+                .add_attribute_value(&"product_id", &"BTC-USD"),
+            body: String::new(),
+            pagination: None,
+        };
+
+        assert_eq!(result, expected);
+    }
+}
@@ -0,0 +1,16 @@
+//! This module contains all `EndPointRequest` and there response type of GDAX API doc under
+//! "Private/Orders" section (<https://docs.gdax.com/#orders>)
+//!
+//! All these end points are private, they require a `RESTClient` created with credentials.
+//! An order is placed by sending one of the `LimitOrder`, `MarketOrder` or `StopOrder` builders.
+
+mod place_order;
+mod cancel_order;
+mod list_orders;
+mod get_order;
+
+pub use self::place_order::{CancelAfter, LimitOrder, MarketOrder, OrderType,
+                            SelfTradePrevention, StopOrder, TimeInForce};
+pub use self::cancel_order::{CancelAllOrders, CancelOrder};
+pub use self::list_orders::ListOrders;
+pub use self::get_order::{GetOrder, Order, OrderStatus};
@@ -0,0 +1,361 @@
+use hyper::Method;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use serde_json;
+use uuid::Uuid;
+
+use products::Side;
+use rest_client::{EndPointRequest, RestRequest};
+use url::Route;
+use super::Order;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
+pub enum OrderType {
+    #[serde(rename = "limit")] Limit,
+    #[serde(rename = "market")] Market,
+    #[serde(rename = "stop")] Stop,
+}
+
+/// The self-trade prevention flag, it defines what happens when two orders from the same user
+/// would match. The default behavior of GDAX is `DecreaseAndCancel`.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
+pub enum SelfTradePrevention {
+    #[serde(rename = "dc")] DecreaseAndCancel,
+    #[serde(rename = "co")] CancelOldest,
+    #[serde(rename = "cn")] CancelNewest,
+    #[serde(rename = "cb")] CancelBoth,
+}
+
+/// The time in force policies of the limit orders.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
+pub enum TimeInForce {
+    #[serde(rename = "GTC")] GoodTillCanceled,
+    #[serde(rename = "GTT")] GoodTillTime,
+    #[serde(rename = "IOC")] ImmediateOrCancel,
+    #[serde(rename = "FOK")] FillOrKill,
+}
+
+/// The possible durations of a `GoodTillTime` order.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
+pub enum CancelAfter {
+    #[serde(rename = "min")] Minute,
+    #[serde(rename = "hour")] Hour,
+    #[serde(rename = "day")] Day,
+}
+
+/// This struct represents the `Place a new order` end point for a limit order.
+/// <https://docs.gdax.com/#place-a-new-order>
+///
+/// The time in force is `GoodTillCanceled` unless one of the builder methods changes it, only
+/// the `GoodTillCanceled` and `GoodTillTime` orders can be post only.
+#[derive(Serialize, PartialEq, Debug)]
+pub struct LimitOrder {
+    #[serde(skip_serializing_if = "Option::is_none")] pub(crate) client_oid: Option<Uuid>,
+    #[serde(rename = "type")] pub(crate) order_type: OrderType,
+    pub(crate) side: Side,
+    pub(crate) product_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")] pub(crate) stp: Option<SelfTradePrevention>,
+    pub(crate) price: Decimal,
+    pub(crate) size: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")] pub(crate) time_in_force: Option<TimeInForce>,
+    #[serde(skip_serializing_if = "Option::is_none")] pub(crate) cancel_after: Option<CancelAfter>,
+    #[serde(skip_serializing_if = "Option::is_none")] pub(crate) post_only: Option<bool>,
+}
+
+impl LimitOrder {
+    pub fn new(product_id: String, side: Side, price: Decimal, size: Decimal) -> LimitOrder {
+        LimitOrder {
+            client_oid: None,
+            order_type: OrderType::Limit,
+            side,
+            product_id,
+            stp: None,
+            price,
+            size,
+            time_in_force: None,
+            cancel_after: None,
+            post_only: None,
+        }
+    }
+
+    /// Set the order id that will be returned in the websocket feed messages.
+    pub fn client_oid(mut self, client_oid: Uuid) -> LimitOrder {
+        self.client_oid = Some(client_oid);
+        self
+    }
+
+    pub fn self_trade_prevention(mut self, stp: SelfTradePrevention) -> LimitOrder {
+        self.stp = Some(stp);
+        self
+    }
+
+    /// The order stays in the book until it is filled or canceled, a post only order is
+    /// rejected if it would take liquidity.
+    pub fn good_till_canceled(mut self, post_only: bool) -> LimitOrder {
+        self.time_in_force = Some(TimeInForce::GoodTillCanceled);
+        self.cancel_after = None;
+        self.post_only = Some(post_only);
+        self
+    }
+
+    /// The order is canceled after `cancel_after` if it is not filled, a post only order is
+    /// rejected if it would take liquidity.
+    pub fn good_till_time(mut self, cancel_after: CancelAfter, post_only: bool) -> LimitOrder {
+        self.time_in_force = Some(TimeInForce::GoodTillTime);
+        self.cancel_after = Some(cancel_after);
+        self.post_only = Some(post_only);
+        self
+    }
+
+    /// The part of the order that cannot be filled immediately is canceled.
+    pub fn immediate_or_cancel(mut self) -> LimitOrder {
+        self.time_in_force = Some(TimeInForce::ImmediateOrCancel);
+        self.cancel_after = None;
+        self.post_only = None;
+        self
+    }
+
+    /// The order is canceled if it cannot be entirely filled immediately.
+    pub fn fill_or_kill(mut self) -> LimitOrder {
+        self.time_in_force = Some(TimeInForce::FillOrKill);
+        self.cancel_after = None;
+        self.post_only = None;
+        self
+    }
+}
+
+/// This struct represents the `Place a new order` end point for a market order, the amount is
+/// either a size in base currency or funds in quote currency.
+/// <https://docs.gdax.com/#place-a-new-order>
+#[derive(Serialize, PartialEq, Debug)]
+pub struct MarketOrder {
+    #[serde(skip_serializing_if = "Option::is_none")] pub(crate) client_oid: Option<Uuid>,
+    #[serde(rename = "type")] pub(crate) order_type: OrderType,
+    pub(crate) side: Side,
+    pub(crate) product_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")] pub(crate) stp: Option<SelfTradePrevention>,
+    #[serde(skip_serializing_if = "Option::is_none")] pub(crate) size: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")] pub(crate) funds: Option<Decimal>,
+}
+
+impl MarketOrder {
+    /// Create a market order to buy or sell `size` of the base currency.
+    pub fn with_size(product_id: String, side: Side, size: Decimal) -> MarketOrder {
+        MarketOrder {
+            client_oid: None,
+            order_type: OrderType::Market,
+            side,
+            product_id,
+            stp: None,
+            size: Some(size),
+            funds: None,
+        }
+    }
+
+    /// Create a market order to buy or sell for `funds` of the quote currency.
+    pub fn with_funds(product_id: String, side: Side, funds: Decimal) -> MarketOrder {
+        MarketOrder {
+            client_oid: None,
+            order_type: OrderType::Market,
+            side,
+            product_id,
+            stp: None,
+            size: None,
+            funds: Some(funds),
+        }
+    }
+
+    /// Set the order id that will be returned in the websocket feed messages.
+    pub fn client_oid(mut self, client_oid: Uuid) -> MarketOrder {
+        self.client_oid = Some(client_oid);
+        self
+    }
+
+    pub fn self_trade_prevention(mut self, stp: SelfTradePrevention) -> MarketOrder {
+        self.stp = Some(stp);
+        self
+    }
+}
+
+/// This struct represents the `Place a new order` end point for a stop order, it becomes a
+/// market order when the last trade price reaches the stop price.
+/// <https://docs.gdax.com/#place-a-new-order>
+#[derive(Serialize, PartialEq, Debug)]
+pub struct StopOrder {
+    #[serde(skip_serializing_if = "Option::is_none")] pub(crate) client_oid: Option<Uuid>,
+    #[serde(rename = "type")] pub(crate) order_type: OrderType,
+    pub(crate) side: Side,
+    pub(crate) product_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")] pub(crate) stp: Option<SelfTradePrevention>,
+    #[serde(rename = "price")] pub(crate) stop_price: Decimal,
+    #[serde(skip_serializing_if = "Option::is_none")] pub(crate) size: Option<Decimal>,
+    #[serde(skip_serializing_if = "Option::is_none")] pub(crate) funds: Option<Decimal>,
+}
+
+impl StopOrder {
+    /// Create a stop order to buy or sell `size` of the base currency.
+    pub fn with_size(
+        product_id: String,
+        side: Side,
+        stop_price: Decimal,
+        size: Decimal,
+    ) -> StopOrder {
+        StopOrder {
+            client_oid: None,
+            order_type: OrderType::Stop,
+            side,
+            product_id,
+            stp: None,
+            stop_price,
+            size: Some(size),
+            funds: None,
+        }
+    }
+
+    /// Create a stop order to buy or sell for `funds` of the quote currency.
+    pub fn with_funds(
+        product_id: String,
+        side: Side,
+        stop_price: Decimal,
+        funds: Decimal,
+    ) -> StopOrder {
+        StopOrder {
+            client_oid: None,
+            order_type: OrderType::Stop,
+            side,
+            product_id,
+            stp: None,
+            stop_price,
+            size: None,
+            funds: Some(funds),
+        }
+    }
+
+    /// Set the order id that will be returned in the websocket feed messages.
+    pub fn client_oid(mut self, client_oid: Uuid) -> StopOrder {
+        self.client_oid = Some(client_oid);
+        self
+    }
+
+    pub fn self_trade_prevention(mut self, stp: SelfTradePrevention) -> StopOrder {
+        self.stp = Some(stp);
+        self
+    }
+}
+
+fn place_order_request<T: Serialize>(order: &T) -> RestRequest {
+    RestRequest {
+        http_method: Method::Post,
+        route: Route::new().add_segment(&"orders"),
+        // the serialization of the builders cannot fail
+        body: serde_json::to_string(order).unwrap(),
+        pagination: None,
+    }
+}
+
+impl EndPointRequest<Order> for LimitOrder {
+    fn create_request(&self) -> RestRequest {
+        place_order_request(self)
+    }
+
+    fn requires_authentication(&self) -> bool {
+        true
+    }
+}
+
+impl EndPointRequest<Order> for MarketOrder {
+    fn create_request(&self) -> RestRequest {
+        place_order_request(self)
+    }
+
+    fn requires_authentication(&self) -> bool {
+        true
+    }
+}
+
+impl EndPointRequest<Order> for StopOrder {
+    fn create_request(&self) -> RestRequest {
+        place_order_request(self)
+    }
+
+    fn requires_authentication(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::Method;
+    use uuid::Uuid;
+
+    use super::{CancelAfter, EndPointRequest, LimitOrder, MarketOrder, RestRequest, Route,
+                SelfTradePrevention, Side, StopOrder};
+
+    fn expected_request(body: &str) -> RestRequest {
+        RestRequest {
+            http_method: Method::Post,
+            route: Route::new().add_segment(&"orders"),
+            body: String::from(body),
+            pagination: None,
+        }
+    }
+
+    #[test]
+    fn test_create_limit_order_request() {
+        let result = LimitOrder::new(String::from("BTC-USD"), Side::Buy, dec!(100.5), dec!(0.01))
+            .client_oid(Uuid::parse_str("d50ec984-77a8-460a-b958-66f114b0de9b").unwrap())
+            .good_till_time(CancelAfter::Hour, true)
+            .create_request();
+
+        let expected = expected_request(
+            "{\"client_oid\":\"d50ec984-77a8-460a-b958-66f114b0de9b\",\"type\":\"limit\",\
+             \"side\":\"buy\",\"product_id\":\"BTC-USD\",\"price\":\"100.5\",\"size\":\"0.01\",\
+             \"time_in_force\":\"GTT\",\"cancel_after\":\"hour\",\"post_only\":true}",
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_limit_order_time_in_force_override() {
+        let result = LimitOrder::new(String::from("BTC-USD"), Side::Sell, dec!(100.0), dec!(1.0))
+            .good_till_time(CancelAfter::Day, true)
+            .immediate_or_cancel()
+            .create_request();
+
+        let expected = expected_request(
+            "{\"type\":\"limit\",\"side\":\"sell\",\"product_id\":\"BTC-USD\",\"price\":\"100.0\",\
+             \"size\":\"1.0\",\"time_in_force\":\"IOC\"}",
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_market_order_request() {
+        let result = MarketOrder::with_funds(String::from("BTC-USD"), Side::Buy, dec!(50.0))
+            .self_trade_prevention(SelfTradePrevention::CancelOldest)
+            .create_request();
+
+        let expected = expected_request(
+            "{\"type\":\"market\",\"side\":\"buy\",\"product_id\":\"BTC-USD\",\"stp\":\"co\",\
+             \"funds\":\"50.0\"}",
+        );
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_stop_order_request() {
+        let result =
+            StopOrder::with_size(String::from("BTC-USD"), Side::Sell, dec!(90.0), dec!(0.5))
+            .create_request();
+
+        let expected = expected_request(
+            "{\"type\":\"stop\",\"side\":\"sell\",\"product_id\":\"BTC-USD\",\"price\":\"90.0\",\
+             \"size\":\"0.5\"}",
+        );
+
+        assert_eq!(result, expected);
+    }
+}
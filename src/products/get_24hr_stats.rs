@@ -1,6 +1,7 @@
 use hyper::Method;
+use rust_decimal::Decimal;
 
-use serde_util::deserialize_from_str;
+use serde_util::{deserialize_from_str, serialize_to_str};
 use rest_client::{EndPointRequest, RestRequest};
 use url::Route;
 
@@ -14,11 +15,22 @@ impl Get24hrStats {
     }
 }
 
+/// The statistics of a product over the last 24 hours, the volume is in base currency units.
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 pub struct Stats {
-    #[serde(deserialize_with = "deserialize_from_str")] open: f64,
-    #[serde(deserialize_with = "deserialize_from_str")] high: f64,
-    #[serde(deserialize_with = "deserialize_from_str")] volume: f64,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub open: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub high: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub low: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub volume: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub last: Decimal,
+    /// The volume of the last 30 days
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub volume_30day: Decimal,
 }
 
 impl EndPointRequest<Stats> for Get24hrStats {
@@ -65,15 +77,31 @@ mod tests {
     \"open\": \"34.19000000\",
     \"high\": \"95.70000000\",
     \"low\": \"7.06000000\",
-    \"volume\": \"2.41000000\"
+    \"volume\": \"2.41000000\",
+    \"last\": \"80.64000000\",
+    \"volume_30day\": \"1105.27010000\"
 }",
         ).unwrap();
         let expected = Stats {
-            open: 34.19,
-            high: 95.7,
-            volume: 2.41,
+            open: dec!(34.19),
+            high: dec!(95.7),
+            low: dec!(7.06),
+            volume: dec!(2.41),
+            last: dec!(80.64),
+            volume_30day: dec!(1105.2701),
         };
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let json = concat!(
+            r#"{"open":"34.19000000","high":"95.70000000","low":"7.06000000","#,
+            r#""volume":"2.41000000","last":"80.64000000","volume_30day":"1105.27010000"}"#,
+        );
+        let result: Stats = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&result).unwrap(), json);
+    }
 }
@@ -1,76 +1,190 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use chrono::serde::ts_seconds;
+use futures::{stream, Future, Stream};
 use hyper::Method;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Serialize, Serializer};
+use serde::ser::SerializeTuple;
 
-use rest_client::{EndPointRequest, RestRequest};
+use error::RestError;
+use rest_client::{EndPointRequest, RESTClient, RestRequest};
 use url::Route;
 
+/// The maximum number of candles returned by GDAX for one request
+pub const MAX_CANDLES: i64 = 300;
+
+/// This struct represents the `Get Historic Rates` end point.
+/// <https://docs.gdax.com/#get-historic-rates>
+///
+/// Without range GDAX returns the latest candles. A range must not contain more than
+/// `MAX_CANDLES` candles, use `RESTClient::get_candles` to fetch a longer range.
 pub struct GetHistoricRates {
     product_id: String,
-    // TODO: put as Option (as it is not required by the API)
-    start: DateTime<Utc>,
-    end: DateTime<Utc>,
-    granularity: u64,
+    range: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    granularity: Granularity,
+}
+
+/// The time slices accepted by GDAX for the candles
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+pub enum Granularity {
+    OneMinute = 60,
+    FiveMinutes = 300,
+    FifteenMinutes = 900,
+    OneHour = 3600,
+    SixHours = 21_600,
+    OneDay = 86_400,
+}
+
+impl Granularity {
+    /// Returns the duration of a candle in seconds
+    pub fn seconds(&self) -> i64 {
+        *self as i64
+    }
+
+    /// Returns the duration of a candle
+    pub fn duration(&self) -> Duration {
+        Duration::seconds(self.seconds())
+    }
 }
 
 impl GetHistoricRates {
-    pub fn new(
-        product_id: String,
-        start: DateTime<Utc>,
-        end: DateTime<Utc>,
-        granularity: u64,
-    ) -> GetHistoricRates {
+    pub fn new(product_id: String, granularity: Granularity) -> GetHistoricRates {
         GetHistoricRates {
             product_id,
-            start,
-            end,
+            range: None,
             granularity,
         }
     }
+
+    /// Request the candles between `start` and `end`
+    pub fn range(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> GetHistoricRates {
+        self.range = Some((start, end));
+        self
+    }
+
+    /// Split the range between `start` and `end` in requests of at most `MAX_CANDLES` candles,
+    /// the first request starts at `start`. Each window starts with the last candle of the
+    /// previous one because GDAX includes both bounds of the range.
+    pub fn windows(
+        product_id: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        granularity: Granularity,
+    ) -> Vec<GetHistoricRates> {
+        let span = granularity.duration() * (MAX_CANDLES - 1) as i32;
+        let mut windows = Vec::new();
+        let mut window_start = start;
+        while window_start < end {
+            let window_end = ::std::cmp::min(window_start + span, end);
+            windows.push(
+                GetHistoricRates::new(String::from(product_id), granularity)
+                    .range(window_start, window_end),
+            );
+            window_start = window_end;
+        }
+        windows
+    }
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// A bucket of the historic rates, GDAX sends it as an array of numbers:
+/// `[time, low, high, open, close, volume]`.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
 pub struct Candle {
-    time: u64, // TODO: change it to Datetime??
-    low: f64,
-    high: f64,
-    open: f64,
-    close: f64,
-    volume: f64,
+    /// The start of the bucket
+    #[serde(with = "ts_seconds")] pub time: DateTime<Utc>,
+    pub low: Decimal,
+    pub high: Decimal,
+    pub open: Decimal,
+    pub close: Decimal,
+    pub volume: Decimal,
+}
+
+impl Serialize for Candle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut candle = serializer.serialize_tuple(6)?;
+        candle.serialize_element(&self.time.timestamp())?;
+        for value in &[self.low, self.high, self.open, self.close, self.volume] {
+            // the candles are the only amounts that GDAX sends as json numbers
+            candle.serialize_element(&value.to_f64())?;
+        }
+        candle.end()
+    }
 }
 
 impl EndPointRequest<Vec<Candle>> for GetHistoricRates {
     fn create_request(&self) -> RestRequest {
+        let mut route = Route::new()
+            .add_segment(&"products")
+            .add_segment(&self.product_id)
+            .add_segment(&"candles");
+        if let Some((start, end)) = self.range {
+            route = route
+                .add_attribute_value(&"start", &start.naive_utc())
+                .add_attribute_value(&"end", &end.naive_utc());
+        }
+
         RestRequest {
             http_method: Method::Get,
-            route: Route::new()
-                .add_segment(&"products")
-                .add_segment(&self.product_id)
-                .add_segment(&"candles")
-                .add_attribute_value(&"start", &self.start.naive_utc())
-                .add_attribute_value(&"end", &self.end.naive_utc())
-                .add_attribute_value(&"granularity", &self.granularity),
+            route: route.add_attribute_value(&"granularity", &self.granularity.seconds()),
             body: String::new(),
             pagination: None,
         }
     }
 }
 
+impl RESTClient {
+    /// Returns the candles of a product between `start` and `end` sorted by time, whatever the
+    /// length of the range.
+    ///
+    /// The range is split in windows of `MAX_CANDLES` candles which are requested one after the
+    /// other, so the requests are throttled by the rate limiter of the client. The candles
+    /// returned twice (on the bounds of the windows) are kept once.
+    pub fn get_candles(
+        &mut self,
+        product_id: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        granularity: Granularity,
+    ) -> Box<Future<Item = Vec<Candle>, Error = RestError>> {
+        let mut client = self.clone();
+        let windows = GetHistoricRates::windows(product_id, start, end, granularity);
+
+        let candles = stream::iter_ok(windows)
+            .and_then(move |window| client.send_request(&window))
+            .concat2()
+            .map(|mut candles: Vec<Candle>| {
+                candles.sort_by_key(|candle| candle.time);
+                candles.dedup_by_key(|candle| candle.time);
+                candles
+            });
+
+        Box::new(candles)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use hyper::Method;
     use chrono::{TimeZone, Utc};
+    use mockito::{mock, SERVER_URL};
     use serde_json;
+    use tokio_core::reactor::Core;
 
-    use super::{Candle, EndPointRequest, GetHistoricRates, RestRequest, Route};
+    use super::{Candle, EndPointRequest, GetHistoricRates, Granularity, RESTClient, RestRequest,
+                Route, MAX_CANDLES};
 
     #[test]
     fn test_create_request() {
-        let result = GetHistoricRates::new(
-            String::from("BTC-USD"),
-            Utc.ymd(2014, 11, 07).and_hms_micro(22, 19, 28, 578_544),
-            Utc.ymd(2014, 11, 07).and_hms_micro(22, 20, 28, 1),
-            1,
-        ).create_request();
+        let result = GetHistoricRates::new(String::from("BTC-USD"), Granularity::OneMinute)
+            .range(
+                Utc.ymd(2014, 11, 07).and_hms_micro(22, 19, 28, 578_544),
+                Utc.ymd(2014, 11, 07).and_hms_micro(22, 20, 28, 1),
+            )
+            .create_request();
 
         let expected = RestRequest {
             http_method: Method::Get,
@@ -80,7 +194,7 @@ mod tests {
                 .add_segment(&"candles")
                 .add_attribute_value(&"start", &"2014-11-07 22:19:28.578544")
                 .add_attribute_value(&"end", &"2014-11-07 22:20:28.000001")
-                .add_attribute_value(&"granularity", &1),
+                .add_attribute_value(&"granularity", &60),
             body: String::new(),
             pagination: None,
         };
@@ -88,6 +202,108 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_create_request_without_range() {
+        let result =
+            GetHistoricRates::new(String::from("BTC-USD"), Granularity::OneDay).create_request();
+
+        let expected = RestRequest {
+            http_method: Method::Get,
+            route: Route::new()
+                .add_segment(&"products")
+                .add_segment(&"BTC-USD")
+                .add_segment(&"candles")
+                .add_attribute_value(&"granularity", &86_400),
+            body: String::new(),
+            pagination: None,
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_windows() {
+        let start = Utc.ymd(2017, 12, 21).and_hms(10, 0, 0);
+        let end = Utc.ymd(2017, 12, 22).and_hms(9, 0, 0);
+
+        let result: Vec<String> =
+            GetHistoricRates::windows("BTC-USD", start, end, Granularity::FiveMinutes)
+                .iter()
+                .map(|window| window.create_request().route.to_string())
+                .collect();
+
+        let expected = vec![
+            String::from(
+                "/products/BTC-USD/candles?start=2017-12-21%2010:00:00\
+                 &end=2017-12-22%2009:00:00&granularity=300",
+            ),
+        ];
+        assert_eq!(result, expected);
+
+        let result = GetHistoricRates::windows("BTC-USD", start, end, Granularity::OneMinute);
+        assert_eq!(result.len(), 5);
+        assert_eq!(
+            result[4].range,
+            Some((start + ::chrono::Duration::minutes(4 * 299), end))
+        );
+        assert!(GetHistoricRates::windows("BTC-USD", end, start, Granularity::OneDay).is_empty());
+    }
+
+    #[test]
+    fn test_windows_candle_count() {
+        let start = Utc.ymd(2017, 1, 1).and_hms(0, 0, 0);
+        let granularities = [
+            Granularity::OneMinute,
+            Granularity::FiveMinutes,
+            Granularity::FifteenMinutes,
+            Granularity::OneHour,
+            Granularity::SixHours,
+            Granularity::OneDay,
+        ];
+
+        for &granularity in &granularities {
+            let end = start + granularity.duration() * 1000;
+            for window in GetHistoricRates::windows("BTC-USD", start, end, granularity) {
+                let (window_start, window_end) = window.range.unwrap();
+                // GDAX returns the candles of both bounds
+                let candles = (window_end - window_start).num_seconds() / granularity.seconds() + 1;
+                assert!(candles <= MAX_CANDLES, "{} candles in {:?}", candles, window.range);
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_candles() {
+        let _m1 = mock(
+            "GET",
+            "/products/BTC-USD/candles?start=2017-12-21%2010:00:00\
+             &end=2017-12-21%2014:59:00&granularity=60",
+        ).with_body("[[1513868340, 1, 1, 1, 1, 1], [1513850400, 2, 2, 2, 2, 2]]")
+            .create();
+        let _m2 = mock(
+            "GET",
+            "/products/BTC-USD/candles?start=2017-12-21%2014:59:00\
+             &end=2017-12-21%2016:00:00&granularity=60",
+        ).with_body("[[1513872000, 3, 3, 3, 3, 3], [1513868340, 1, 1, 1, 1, 1]]")
+            .create();
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+
+        let mut client = RESTClient::new(SERVER_URL, &handle).unwrap();
+        client.set_rate_limiter(None);
+
+        let result = core.run(client.get_candles(
+            "BTC-USD",
+            Utc.ymd(2017, 12, 21).and_hms(10, 0, 0),
+            Utc.ymd(2017, 12, 21).and_hms(16, 0, 0),
+            Granularity::OneMinute,
+        )).unwrap();
+
+        let times: Vec<i64> = result.iter().map(|candle| candle.time.timestamp()).collect();
+        assert_eq!(times, vec![1513850400, 1513868340, 1513872000]);
+        assert_eq!(result[2].close, dec!(3));
+    }
+
     #[test]
     fn test_deserialize() {
         let result: Vec<Candle> = serde_json::from_str(
@@ -98,23 +314,34 @@ mod tests {
         ).unwrap();
         let expected = vec![
             Candle {
-                time: 1415398768,
-                low: 0.32,
-                high: 4.2,
-                open: 0.35,
-                close: 4.2,
-                volume: 12.3,
+                time: Utc.timestamp(1415398768, 0),
+                low: dec!(0.32),
+                high: dec!(4.2),
+                open: dec!(0.35),
+                close: dec!(4.2),
+                volume: dec!(12.3),
             },
             Candle {
-                time: 1415398769,
-                low: 0.33,
-                high: 4.3,
-                open: 0.36,
-                close: 4.2,
-                volume: 12.3,
+                time: Utc.timestamp(1415398769, 0),
+                low: dec!(0.33),
+                high: dec!(4.3),
+                open: dec!(0.36),
+                close: dec!(4.2),
+                volume: dec!(12.3),
             },
         ];
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let json = concat!(
+            r#"[[1415398768,0.32,4.2,0.35,4.2,12.3],[1415398769,0.33,4.3,0.36,4.2,"#,
+            r#"12.3]]"#,
+        );
+        let result: Vec<Candle> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&result).unwrap(), json);
+    }
 }
@@ -1,4 +1,5 @@
 use hyper::Method;
+use rust_decimal::Decimal;
 
 use serde_util::deserialize_from_str;
 use rest_client::{EndPointRequest, RestRequest};
@@ -8,14 +9,16 @@ use serde;
 use serde_json;
 
 /// This struct represent the endpoint `Get Product Order Book` <https://docs.gdax.com/#get-product-order-book>
-/// (The level 3 requests are not implemented)
+/// (The level 3 book should be maintained from the websocket feed with
+/// `order_book::FullOrderBook`)
 pub struct GetProductOrderBook {
     pub product_id: String,
     pub level: Level,
 }
 
-/// This enum represents the order book possible levels to request.
-#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
+/// This enum represents the order book possible levels to request, it is serialized as the
+/// number GDAX expects.
+#[derive(PartialEq, Debug, Copy, Clone)]
 pub enum Level {
     /// Only the best bid and ask (aggregated)
     /// The size field is the sum of the size of the orders at that price, and num-orders is the count of orders at that price; size should not be multiplied by num-orders.
@@ -27,7 +30,30 @@ pub enum Level {
 
     /// Full order book (non aggregated)
     /// Level 3 is only recommended for users wishing to maintain a full real-time order book using the websocket stream. Abuse of Level 3 via polling will cause your access to be limited or blocked.
-    Full = 3, // TODO: Handle level 3 (with enum)
+    Full = 3,
+}
+
+impl serde::Serialize for Level {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Level {
+    fn deserialize<D>(deserializer: D) -> Result<Level, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match serde::Deserialize::deserialize(deserializer)? {
+            1u8 => Ok(Level::Best),
+            2 => Ok(Level::Top50),
+            3 => Ok(Level::Full),
+            level => Err(serde::de::Error::custom(format!("Unknown level {}", level))),
+        }
+    }
 }
 
 impl GetProductOrderBook {
@@ -36,14 +62,14 @@ impl GetProductOrderBook {
     }
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct OrderBook<T> {
     pub sequence: usize,
     pub bids: Vec<T>,
     pub asks: Vec<T>,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
 pub enum OrderInfo {
     #[serde(rename = "num_orders")] Count(i64),
     #[serde(rename = "order_id")] Id(Uuid),
@@ -68,13 +94,33 @@ where
     Err(serde::de::Error::custom("Unexpected value"))
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// A price level of the book, GDAX sends it as an array: `[price, size, num_orders]` for the
+/// aggregated levels and `[price, size, order_id]` for the level 3 book.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
 pub struct PriceLevel {
-    #[serde(deserialize_with = "deserialize_from_str")] pub price: f64,
-    #[serde(deserialize_with = "deserialize_from_str")] pub size: f64,
+    #[serde(deserialize_with = "deserialize_from_str")] pub price: Decimal,
+    #[serde(deserialize_with = "deserialize_from_str")] pub size: Decimal,
     #[serde(deserialize_with = "deserialize_orderinfo")] pub orderinfo: OrderInfo,
 }
 
+impl serde::Serialize for PriceLevel {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeTuple;
+
+        let mut level = serializer.serialize_tuple(3)?;
+        level.serialize_element(&self.price.to_string())?;
+        level.serialize_element(&self.size.to_string())?;
+        match self.orderinfo {
+            OrderInfo::Count(count) => level.serialize_element(&count)?,
+            OrderInfo::Id(ref id) => level.serialize_element(id)?,
+        }
+        level.end()
+    }
+}
+
 impl EndPointRequest<OrderBook<PriceLevel>> for GetProductOrderBook {
     fn create_request(&self) -> RestRequest {
         RestRequest {
@@ -138,30 +184,30 @@ mod tests {
             sequence: 3,
             bids: vec![
                 PriceLevel {
-                    price: 16839.45,
-                    size: 0.47037038,
+                    price: dec!(16839.45),
+                    size: dec!(0.47037038),
                     orderinfo: OrderInfo::Count(2),
                 },
                 PriceLevel {
-                    price: 16835.39,
-                    size: 0.00075522,
+                    price: dec!(16835.39),
+                    size: dec!(0.00075522),
                     orderinfo: OrderInfo::Count(2),
                 },
             ],
             asks: vec![
                 PriceLevel {
-                    price: 16913.21,
-                    size: 4.85,
+                    price: dec!(16913.21),
+                    size: dec!(4.85),
                     orderinfo: OrderInfo::Count(1),
                 },
                 PriceLevel {
-                    price: 16918.01,
-                    size: 0.70301839,
+                    price: dec!(16918.01),
+                    size: dec!(0.70301839),
                     orderinfo: OrderInfo::Count(11),
                 },
                 PriceLevel {
-                    price: 16918.02,
-                    size: 9.88197274,
+                    price: dec!(16918.02),
+                    size: dec!(9.88197274),
                     orderinfo: OrderInfo::Count(24),
                 },
             ],
@@ -191,15 +237,15 @@ mod tests {
             sequence: 3,
             bids: vec![
                 PriceLevel {
-                    price: 16839.45,
-                    size: 0.47037038,
+                    price: dec!(16839.45),
+                    size: dec!(0.47037038),
                     orderinfo: OrderInfo::Id(
                         Uuid::parse_str("3b0f1225-7f84-490b-a29f-0faef9de823a").unwrap(),
                     ),
                 },
                 PriceLevel {
-                    price: 16835.39,
-                    size: 0.00075522,
+                    price: dec!(16835.39),
+                    size: dec!(0.00075522),
                     orderinfo: OrderInfo::Id(
                         Uuid::parse_str("3b0f1225-7f84-490b-a29f-1faef9de823a").unwrap(),
                     ),
@@ -207,22 +253,22 @@ mod tests {
             ],
             asks: vec![
                 PriceLevel {
-                    price: 16913.21,
-                    size: 4.85,
+                    price: dec!(16913.21),
+                    size: dec!(4.85),
                     orderinfo: OrderInfo::Id(
                         Uuid::parse_str("da863862-25f4-4868-ac41-005d11ab0a5f").unwrap(),
                     ),
                 },
                 PriceLevel {
-                    price: 16918.01,
-                    size: 0.70301839,
+                    price: dec!(16918.01),
+                    size: dec!(0.70301839),
                     orderinfo: OrderInfo::Id(
                         Uuid::parse_str("da863862-25f4-4868-ac41-005d11ab1a5f").unwrap(),
                     ),
                 },
                 PriceLevel {
-                    price: 16918.02,
-                    size: 9.88197274,
+                    price: dec!(16918.02),
+                    size: dec!(9.88197274),
                     orderinfo: OrderInfo::Id(
                         Uuid::parse_str("da863862-25f4-4868-ac41-005d11ab2a5f").unwrap(),
                     ),
@@ -231,4 +277,22 @@ mod tests {
         };
         assert_eq!(result, expected)
     }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let json = concat!(
+            r#"{"sequence":3,"bids":[["16839.45","0.47037038",2]],"asks":[["16913.21","#,
+            r#""4.85000000","da863862-25f4-4868-ac41-005d11ab0a5f"]]}"#,
+        );
+        let result: OrderBook<PriceLevel> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&result).unwrap(), json);
+    }
+
+    #[test]
+    fn test_serialize_level() {
+        assert_eq!(serde_json::to_string(&Level::Top50).unwrap(), "2");
+        assert_eq!(serde_json::from_str::<Level>("3").unwrap(), Level::Full);
+        assert!(serde_json::from_str::<Level>("4").is_err());
+    }
 }
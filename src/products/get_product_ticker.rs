@@ -1,7 +1,8 @@
 use hyper::Method;
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 
-use serde_util::deserialize_from_str;
+use serde_util::{deserialize_from_str, serialize_to_str};
 use rest_client::{EndPointRequest, RestRequest};
 use url::Route;
 
@@ -21,11 +22,16 @@ impl GetProductTicker {
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 pub struct Ticker {
     pub trade_id: usize,
-    #[serde(deserialize_with = "deserialize_from_str")] pub price: f64,
-    #[serde(deserialize_with = "deserialize_from_str")] pub size: f64,
-    #[serde(deserialize_with = "deserialize_from_str")] pub bid: f64,
-    #[serde(deserialize_with = "deserialize_from_str")] pub ask: f64,
-    #[serde(deserialize_with = "deserialize_from_str")] pub volume: f64,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub price: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub size: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub bid: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub ask: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub volume: Decimal,
     pub time: DateTime<Utc>,
 }
 
@@ -85,15 +91,26 @@ mod tests {
 
         let expected = Ticker {
             trade_id: 4729088,
-            price: 333.99,
-            size: 0.193,
-            bid: 333.98,
-            ask: 333.99,
-            volume: 5957.11914015,
+            price: dec!(333.99),
+            size: dec!(0.193),
+            bid: dec!(333.98),
+            ask: dec!(333.99),
+            volume: dec!(5957.11914015),
             time: Utc.ymd(2015, 11, 14).and_hms_micro(20, 46, 3, 511_254),
         };
 
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_serialize_round_trip() {
+        let json = concat!(
+            r#"{"trade_id":4729088,"price":"333.99","size":"0.193","bid":"333.98","#,
+            r#""ask":"333.99","volume":"5957.11914015","#,
+            r#""time":"2015-11-14T20:46:03.511254Z"}"#,
+        );
+        let result: Ticker = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&result).unwrap(), json);
+    }
 }
@@ -1,6 +1,8 @@
 use hyper::Method;
+use rust_decimal::Decimal;
 
-use serde_util::deserialize_from_str;
+use serde_util::{deserialize_from_str, deserialize_option_from_str, serialize_option_to_str,
+                 serialize_to_str};
 use rest_client::{EndPointRequest, RestRequest};
 use url::Route;
 
@@ -21,9 +23,31 @@ pub struct Product {
     pub id: String,
     pub base_currency: String,
     pub quote_currency: String,
-    #[serde(deserialize_with = "deserialize_from_str")] pub base_min_size: f64,
-    #[serde(deserialize_with = "deserialize_from_str")] pub base_max_size: f64,
-    #[serde(deserialize_with = "deserialize_from_str")] pub quote_increment: f64,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub base_min_size: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub base_max_size: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub quote_increment: Decimal,
+    pub display_name: String,
+    /// `online` or `offline`
+    pub status: String,
+    /// Explains why the product is offline or restricted
+    #[serde(default)] pub status_message: Option<String>,
+    /// The minimum funds of a market order
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    #[serde(serialize_with = "serialize_option_to_str")]
+    pub min_market_funds: Option<Decimal>,
+    /// The maximum funds of a market order
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    #[serde(serialize_with = "serialize_option_to_str")]
+    pub max_market_funds: Option<Decimal>,
+    /// Only the orders which add liquidity to the book are accepted
+    #[serde(default)] pub post_only: bool,
+    /// Only the limit orders are accepted
+    #[serde(default)] pub limit_only: bool,
+    /// Only the cancel requests are accepted
+    #[serde(default)] pub cancel_only: bool,
 }
 
 impl EndPointRequest<Vec<Product>> for GetProducts {
@@ -68,7 +92,15 @@ mod tests {
                     "quote_currency": "USD",
                     "base_min_size": "0.01",
                     "base_max_size": "10000.00",
-                    "quote_increment": "0.01"
+                    "quote_increment": "0.01",
+                    "display_name": "BTC/USD",
+                    "status": "online",
+                    "status_message": null,
+                    "min_market_funds": "10",
+                    "max_market_funds": "1000000",
+                    "post_only": false,
+                    "limit_only": false,
+                    "cancel_only": false
                 }
             ]"#,
         ).unwrap();
@@ -77,11 +109,33 @@ mod tests {
                 id: String::from("BTC-USD"),
                 base_currency: String::from("BTC"),
                 quote_currency: String::from("USD"),
-                base_min_size: 0.01,
-                base_max_size: 10000.0,
-                quote_increment: 0.01,
+                base_min_size: dec!(0.01),
+                base_max_size: dec!(10000.0),
+                quote_increment: dec!(0.01),
+                display_name: String::from("BTC/USD"),
+                status: String::from("online"),
+                status_message: None,
+                min_market_funds: Some(dec!(10)),
+                max_market_funds: Some(dec!(1000000)),
+                post_only: false,
+                limit_only: false,
+                cancel_only: false,
             },
         ];
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let json = concat!(
+            r#"[{"id":"BTC-USD","base_currency":"BTC","quote_currency":"USD","#,
+            r#""base_min_size":"0.01","base_max_size":"10000.00","#,
+            r#""quote_increment":"0.01","display_name":"BTC/USD","status":"online","#,
+            r#""status_message":null,"min_market_funds":"10","max_market_funds":"1000000","#,
+            r#""post_only":false,"limit_only":false,"cancel_only":true}]"#,
+        );
+        let result: Vec<Product> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&result).unwrap(), json);
+    }
 }
@@ -1,8 +1,9 @@
 use chrono::{DateTime, Utc};
 use hyper::Method;
+use rust_decimal::Decimal;
 
-use serde_util::deserialize_from_str;
-use rest_client::{EndPointRequest, Pagination, RestRequest};
+use serde_util::{deserialize_from_str, serialize_to_str};
+use rest_client::{EndPointRequest, PaginatedRequest, Pagination, RestRequest};
 use url::Route;
 
 pub struct GetTrades {
@@ -19,7 +20,7 @@ impl GetTrades {
     }
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
 pub enum Side {
     #[serde(rename = "sell")] Sell,
     #[serde(rename = "buy")] Buy,
@@ -29,8 +30,10 @@ pub enum Side {
 pub struct Trade {
     pub time: DateTime<Utc>,
     pub trade_id: usize,
-    #[serde(deserialize_with = "deserialize_from_str")] pub price: f64,
-    #[serde(deserialize_with = "deserialize_from_str")] pub size: f64,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub price: Decimal,
+    #[serde(serialize_with = "serialize_to_str", deserialize_with = "deserialize_from_str")]
+    pub size: Decimal,
     pub side: Side,
 }
 
@@ -48,6 +51,8 @@ impl EndPointRequest<Vec<Trade>> for GetTrades {
     }
 }
 
+impl PaginatedRequest<Trade> for GetTrades {}
+
 #[cfg(test)]
 mod tests {
     use chrono::{TimeZone, Utc};
@@ -93,19 +98,30 @@ mod tests {
             Trade {
                 time: Utc.ymd(2014, 11, 07).and_hms_micro(22, 19, 28, 578_544),
                 trade_id: 74,
-                price: 10.0,
-                size: 0.01,
+                price: dec!(10.0),
+                size: dec!(0.01),
                 side: Side::Buy,
             },
             Trade {
                 time: Utc.ymd(2014, 11, 07).and_hms_micro(1, 8, 43, 642_366),
                 trade_id: 73,
-                price: 100.0,
-                size: 0.01,
+                price: dec!(100.0),
+                size: dec!(0.01),
                 side: Side::Sell,
             },
         ];
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let json = concat!(
+            r#"[{"time":"2014-11-07T22:19:28.578544Z","trade_id":74,"#,
+            r#""price":"10.00000000","size":"0.01000000","side":"buy"}]"#,
+        );
+        let result: Vec<Trade> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&result).unwrap(), json);
+    }
 }
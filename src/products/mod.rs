@@ -9,8 +9,9 @@ mod get_historic_rates;
 mod get_24hr_stats;
 
 pub use self::get_products::{GetProducts, Product};
-pub use self::get_product_order_book::{GetProductOrderBook, Level};
+pub use self::get_product_order_book::{GetProductOrderBook, Level, OrderBook, OrderInfo,
+                                        PriceLevel};
 pub use self::get_product_ticker::{GetProductTicker, Ticker};
 pub use self::get_trades::{GetTrades, Side, Trade};
-pub use self::get_historic_rates::{Candle, GetHistoricRates};
+pub use self::get_historic_rates::{Candle, GetHistoricRates, Granularity, MAX_CANDLES};
 pub use self::get_24hr_stats::{Get24hrStats, Stats};
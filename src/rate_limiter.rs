@@ -0,0 +1,143 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The GDAX limits of the public end points (requests per second and burst)
+const PUBLIC_RATE: f64 = 3.0;
+const PUBLIC_BURST: f64 = 6.0;
+/// The GDAX limits of the private end points (requests per second and burst)
+const PRIVATE_RATE: f64 = 5.0;
+const PRIVATE_BURST: f64 = 10.0;
+
+/// A client side rate limiter with one token bucket for the public end points and one for the
+/// private end points (<https://docs.gdax.com/#rate-limits>).
+///
+/// When a bucket is empty the requests are queued: each request is delayed until a token is
+/// available for it. The limiter can be cloned to share the same buckets between several
+/// `RESTClient`.
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    public: Arc<Mutex<TokenBucket>>,
+    private: Arc<Mutex<TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Create a rate limiter allowing `rate` requests per second with bursts of `burst` requests,
+    /// for the public and the private end points.
+    pub fn new(
+        public_rate: f64,
+        public_burst: f64,
+        private_rate: f64,
+        private_burst: f64,
+    ) -> RateLimiter {
+        RateLimiter {
+            public: Arc::new(Mutex::new(TokenBucket::new(public_rate, public_burst))),
+            private: Arc::new(Mutex::new(TokenBucket::new(private_rate, private_burst))),
+        }
+    }
+
+    /// Take a token for a request and returns how long the request must wait before being sent.
+    pub fn reserve(&self, private: bool) -> Duration {
+        let bucket = if private {
+            &self.private
+        } else {
+            &self.public
+        };
+        bucket.lock().unwrap().reserve(Instant::now())
+    }
+}
+
+impl Default for RateLimiter {
+    /// Returns a rate limiter with the GDAX limits: 3 requests per second for the public end
+    /// points and 5 requests per second for the private ones, with bursts of twice the rate.
+    fn default() -> RateLimiter {
+        RateLimiter::new(PUBLIC_RATE, PUBLIC_BURST, PRIVATE_RATE, PRIVATE_BURST)
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last_update: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, capacity: f64) -> TokenBucket {
+        TokenBucket {
+            rate,
+            capacity,
+            tokens: capacity,
+            last_update: Instant::now(),
+        }
+    }
+
+    /// The tokens can be negative, it means that some requests are already waiting for the next
+    /// tokens.
+    fn reserve(&mut self, now: Instant) -> Duration {
+        if now > self.last_update {
+            let elapsed = now.duration_since(self.last_update);
+            let elapsed = elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) * 1e-9;
+            self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+            self.last_update = now;
+        }
+
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::new(0, 0)
+        } else {
+            let wait = -self.tokens / self.rate;
+            Duration::new(wait.trunc() as u64, (wait.fract() * 1e9) as u32)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{RateLimiter, TokenBucket};
+
+    #[test]
+    fn test_burst() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(2.0, 3.0);
+        bucket.last_update = now;
+
+        assert_eq!(bucket.reserve(now), Duration::new(0, 0));
+        assert_eq!(bucket.reserve(now), Duration::new(0, 0));
+        assert_eq!(bucket.reserve(now), Duration::new(0, 0));
+        assert_eq!(bucket.reserve(now), Duration::from_millis(500));
+        assert_eq!(bucket.reserve(now), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn test_refill() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(2.0, 1.0);
+        bucket.last_update = now;
+
+        assert_eq!(bucket.reserve(now), Duration::new(0, 0));
+        assert_eq!(bucket.reserve(now), Duration::from_millis(500));
+        // the bucket cannot refill above its capacity
+        assert_eq!(
+            bucket.reserve(now + Duration::from_secs(10)),
+            Duration::new(0, 0)
+        );
+        assert_eq!(
+            bucket.reserve(now + Duration::from_secs(10)),
+            Duration::from_millis(500)
+        );
+    }
+
+    #[test]
+    fn test_shared_buckets() {
+        let limiter = RateLimiter::new(1.0, 1.0, 1.0, 1.0);
+        let shared = limiter.clone();
+
+        assert_eq!(limiter.reserve(false), Duration::new(0, 0));
+        assert!(shared.reserve(false) > Duration::new(0, 0));
+        // the private bucket is independent of the public one
+        assert_eq!(shared.reserve(true), Duration::new(0, 0));
+    }
+}
@@ -1,37 +1,44 @@
-use hyper;
+use std::time::Duration;
+
 use serde::de;
 use serde_json;
-use hyper::{Body, Client, Method, Request, Uri};
-use hyper::header::{ContentLength, UserAgent};
+use hyper::{Body, Chunk, Client, Method, Request, StatusCode, Uri};
+use hyper::header::{ContentLength, Headers, UserAgent};
 use hyper::client::HttpConnector;
 use hyper_tls::HttpsConnector;
-use tokio_core::reactor::Handle;
-use futures::{Future, Stream};
-
+use tokio_core::reactor::{Handle, Timeout};
+use futures::{future, stream, Future, Stream};
+use futures::future::Loop;
+
+use auth::{authenticate, Credentials};
+use rate_limiter::RateLimiter;
+use retry::RetryPolicy;
+use session::{Exchange, Session};
 use url::Route;
-use error::RestError;
-// use hyper::header::Headers;
+use error::{ApiError, ParseError, RestError};
 
-const PUBLIC_API: &str = "https://api.gdax.com";
-const SANDBOX_API: &str = "https://api-public.sandbox.gdax.com";
+pub const PUBLIC_API: &str = "https://api.gdax.com";
+pub const SANDBOX_API: &str = "https://api-public.sandbox.gdax.com";
 const USER_AGENT: &str = concat!("gdax_rs/", env!("CARGO_PKG_VERSION"));
 
+#[derive(Clone)]
 pub struct RESTClient {
     api_url: String,
     client: Client<HttpsConnector<HttpConnector>, Body>,
+    handle: Handle,
+    credentials: Option<Credentials>,
+    rate_limiter: Option<RateLimiter>,
+    retry_policy: RetryPolicy,
+    session: Option<Session>,
 }
 
-// header! { (PaginationAfterHeader, "after") => [usize] }
-// header! { (PaginationBeforeHeader, "before") => [usize] }
-// header! { (PaginationLimitHeader, "limit") => [u32] }
-// header! { (PaginationAfterHeader, "CB-AFTER") => [usize] }
-// header! { (PaginationBeforeHeader, "CB-BEFORE") => [usize] }
-// header! { (PaginationLimitHeader, "CB-LIMIT") => [u32] }
-
 // TODO: remove all unwrap and handle error (error chain??)
 impl RESTClient {
     /// Create a new `RESTClient` object with a specified API URL, for most cases, you should use
     /// `RESTClient::default` or `RESTClient::staging` to connect to GDAX
+    ///
+    /// The client is throttled by a `RateLimiter` with the GDAX limits and it retries the
+    /// failed `GET` requests with the default `RetryPolicy`.
     pub fn new(api_url: &str, handle: &Handle) -> Result<RESTClient, RestError> {
         let connector = HttpsConnector::new(4, handle)
             .map_err(|e| RestError::HttpsConnectorError(e.to_string()))?;
@@ -39,9 +46,27 @@ impl RESTClient {
         Ok(RESTClient {
             api_url: String::from(api_url),
             client,
+            handle: handle.clone(),
+            credentials: None,
+            rate_limiter: Some(RateLimiter::default()),
+            retry_policy: RetryPolicy::default(),
+            session: None,
         })
     }
 
+    /// Create a new `RESTClient` object that signs its requests with `credentials`, it is
+    /// required to access the private end points. Use `PUBLIC_API` or `SANDBOX_API` as API URL
+    /// to connect to GDAX.
+    pub fn with_credentials(
+        api_url: &str,
+        credentials: Credentials,
+        handle: &Handle,
+    ) -> Result<RESTClient, RestError> {
+        let mut client = RESTClient::new(api_url, handle)?;
+        client.credentials = Some(credentials);
+        Ok(client)
+    }
+
     /// Returns the default APIConnector (connected to the staging API)
     pub fn default(handle: &Handle) -> RESTClient {
         RESTClient::new(PUBLIC_API, handle).unwrap()
@@ -52,63 +77,366 @@ impl RESTClient {
         RESTClient::new(SANDBOX_API, handle).unwrap()
     }
 
+    /// Replace the rate limiter of the client, `None` disables the throttling. Giving the
+    /// `rate_limiter` of another client makes both clients share the same limits.
+    pub fn set_rate_limiter(&mut self, rate_limiter: Option<RateLimiter>) {
+        self.rate_limiter = rate_limiter;
+    }
+
+    /// Returns the rate limiter of the client
+    pub fn rate_limiter(&self) -> Option<RateLimiter> {
+        self.rate_limiter.clone()
+    }
+
+    /// Replace the retry policy of the client, `RetryPolicy::never()` disables the retries.
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Record the requests of the client in a session or replay a recorded session, `None`
+    /// restores the normal behavior. A replaying client sends no request to GDAX, it does not
+    /// need credentials to send private requests.
+    pub fn set_session(&mut self, session: Option<Session>) {
+        self.session = session;
+    }
+
     /// This method send a request to GDAX API and return the result as a `Future`
+    ///
+    /// The requests of private end points are signed, if the client has no credentials the
+    /// future fails immediately with `RestError::AuthenticationRequired`. The request is
+    /// delayed if the rate limiter has no token left and it is sent again on transient failures
+    /// if it is idempotent.
     pub fn send_request<T: 'static + de::DeserializeOwned>(
         &mut self,
-        request: &EndPointRequest<T>,
-    ) -> Box<Future<Item = T, Error = hyper::Error> + 'static> {
-        let request = request.create_request();
+        request_handler: &EndPointRequest<T>,
+    ) -> Box<Future<Item = T, Error = RestError> + 'static> {
+        let work = self.send(
+            request_handler.create_request(),
+            request_handler.requires_authentication(),
+        ).and_then(|response| parse_body(&response.body));
 
-        let opts = if let Some(pagination) = request.pagination {
-            let mut owned = match pagination.page {
-                Cursor::Before(before) => format!("?before={}", before),
-                Cursor::After(after) => format!("?after={}", after),
-            }.to_owned();
+        Box::new(work)
+    }
+
+    /// Same as `send_request` for the end points returning a list, the result is returned with
+    /// the cursors of the previous and next pages (`CB-BEFORE` and `CB-AFTER` headers).
+    pub fn send_paginated_request<T: 'static + de::DeserializeOwned>(
+        &mut self,
+        request_handler: &EndPointRequest<Vec<T>>,
+    ) -> Box<Future<Item = Page<T>, Error = RestError> + 'static> {
+        let work = self.send(
+            request_handler.create_request(),
+            request_handler.requires_authentication(),
+        ).and_then(|response| {
+            Ok(Page {
+                items: parse_body(&response.body)?,
+                before: response.before,
+                after: response.after,
+            })
+        });
+
+        Box::new(work)
+    }
+
+    /// Returns a `Stream` of all the items of a paginated end point, the pages are requested one
+    /// after the other until GDAX returns an empty page or no cursor.
+    ///
+    /// The pages are walked in the direction of the request cursor: towards the older items by
+    /// default or with `Cursor::After`, towards the newer items with `Cursor::Before`.
+    pub fn paginate<T, R>(
+        &mut self,
+        request_handler: &R,
+    ) -> Box<Stream<Item = T, Error = RestError>>
+    where
+        T: 'static + de::DeserializeOwned,
+        R: PaginatedRequest<T>,
+    {
+        let client = self.clone();
+        let request = request_handler.create_request();
+        let private = request_handler.requires_authentication();
+        let first_pagination = request.pagination;
+        let limit = first_pagination.and_then(|pagination| pagination.limit);
+        let backward = match first_pagination {
+            Some(pagination) => match pagination.page {
+                Cursor::Before(_) => true,
+                Cursor::After(_) => false,
+            },
+            None => false,
+        };
+
+        let pages = stream::unfold(Some(first_pagination), move |pagination| {
+            pagination.map(|pagination| {
+                let mut request = request.clone();
+                request.pagination = pagination;
+                client.send(request, private).and_then(move |response| {
+                    let items: Vec<T> = parse_body(&response.body)?;
+                    let cursor = if backward {
+                        response.before.map(Cursor::Before)
+                    } else {
+                        response.after.map(Cursor::After)
+                    };
+                    let next = match cursor {
+                        Some(page) if !items.is_empty() => Some(Some(Pagination { page, limit })),
+                        _ => None,
+                    };
+                    Ok((items, next))
+                })
+            })
+        });
+
+        Box::new(pages.map(stream::iter_ok).flatten())
+    }
+
+    /// Send the request and returns the http response if its status is a success, the `GET`
+    /// requests are retried according to the retry policy.
+    fn send(
+        &self,
+        request: RestRequest,
+        private: bool,
+    ) -> Box<Future<Item = HttpResponse, Error = RestError> + 'static> {
+        let mut route = request.route;
+        if let Some(pagination) = request.pagination {
+            route = match pagination.page {
+                Cursor::Before(before) => route.add_attribute_value(&"before", &before),
+                Cursor::After(after) => route.add_attribute_value(&"after", &after),
+            };
 
             if let Some(limit) = pagination.limit {
-                owned.push_str(&format!("&limit={}", limit));
+                route = route.add_attribute_value(&"limit", &limit);
             }
+        }
+        let path = route.to_string();
 
-            owned
-        } else {
-            String::new()
+        let replaying = match self.session {
+            Some(ref session) => session.is_replaying(),
+            None => false,
         };
+        if private && self.credentials.is_none() && !replaying {
+            return Box::new(future::err(RestError::AuthenticationRequired(path)));
+        }
 
         // create the full request uri
-        // TODO: remove unwrap
-        let uri: Uri = format!("{}{}{}", self.api_url, request.route.to_string(), &opts)
-            .parse()
-            .unwrap();
+        let uri: Uri = match format!("{}{}", self.api_url, path).parse() {
+            Ok(uri) => uri,
+            Err(uri_error) => return Box::new(future::err(RestError::from(uri_error))),
+        };
+
+        let retry_policy = if request.http_method == Method::Get {
+            self.retry_policy
+        } else {
+            RetryPolicy::never()
+        };
 
-        // create request
-        let mut req = Request::new(request.http_method.clone(), uri);
-        req.headers_mut()
-            .set(ContentLength(request.body.len() as u64));
+        let prepared_request = PreparedRequest {
+            client: self.client.clone(),
+            handle: self.handle.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+            session: self.session.clone(),
+            credentials: if private {
+                self.credentials.clone()
+            } else {
+                None
+            },
+            http_method: request.http_method,
+            uri,
+            path,
+            body: request.body,
+        };
 
-        /*
-        if let Some(pagination) = request.pagination {
-            match pagination.page {
-                Cursor::Before(before) => req.headers_mut().set(PaginationBeforeHeader(before)),
-                Cursor::After(after) => req.headers_mut().set(PaginationAfterHeader(after))
+        let work = future::loop_fn(1, move |attempt| {
+            let backoff = if attempt > 1 {
+                retry_policy.delay(attempt - 1)
+            } else {
+                Duration::new(0, 0)
             };
-            if let Some(limit) = pagination.limit {
-                req.headers_mut().set(PaginationLimitHeader(limit));
+            let max_attempts = retry_policy.max_attempts();
+
+            prepared_request.send(backoff).then(move |result| match result {
+                Err(ref error) if attempt < max_attempts && RetryPolicy::is_retryable(error) => {
+                    Ok(Loop::Continue(attempt + 1))
+                }
+                Err(error) => Err(error),
+                Ok(response) => Ok(Loop::Break(response)),
+            })
+        });
+
+        Box::new(work)
+    }
+}
+
+/// All that is needed to send a request, possibly several times
+struct PreparedRequest {
+    client: Client<HttpsConnector<HttpConnector>, Body>,
+    handle: Handle,
+    rate_limiter: Option<RateLimiter>,
+    session: Option<Session>,
+    credentials: Option<Credentials>,
+    http_method: Method,
+    uri: Uri,
+    path: String,
+    body: String,
+}
+
+impl PreparedRequest {
+    /// Wait for `backoff` then for the rate limiter and send the request
+    fn send(&self, backoff: Duration) -> Box<Future<Item = HttpResponse, Error = RestError>> {
+        if let Some(ref session) = self.session {
+            if session.is_replaying() {
+                return Box::new(future::result(self.replay(session)));
             }
         }
-        */
-
-        req.set_body(request.body.clone());
 
-        // set the user agent (required by the API)
-        req.headers_mut().set(UserAgent::new(USER_AGENT));
+        let client = self.client.clone();
+        let handle = self.handle.clone();
+        let rate_limiter = self.rate_limiter.clone();
+        let credentials = self.credentials.clone();
+        let http_method = self.http_method.clone();
+        let uri = self.uri.clone();
+        let path = self.path.clone();
+        let body = self.body.clone();
+        let private = credentials.is_some();
+        let session = self.session.clone();
+        let recorded_request = Exchange {
+            method: self.http_method.to_string(),
+            route: self.path.clone(),
+            body: self.body.clone(),
+            status: 0,
+            before: None,
+            after: None,
+            response: String::new(),
+        };
 
-        let work = self.client
-            .request(req)
-            .and_then(|res| res.body().concat2())
-            .and_then(|body| Ok(serde_json::from_slice(&body).unwrap()));
+        let work = sleep(backoff, &self.handle)
+            .and_then(move |_| {
+                let delay = match rate_limiter {
+                    Some(ref rate_limiter) => rate_limiter.reserve(private),
+                    None => Duration::new(0, 0),
+                };
+                sleep(delay, &handle)
+            })
+            .and_then(move |_| {
+                // create request, it is signed after the delays to get a valid timestamp
+                let mut req = Request::new(http_method, uri);
+                req.headers_mut().set(ContentLength(body.len() as u64));
+
+                if let Some(ref credentials) = credentials {
+                    authenticate(credentials, &mut req, &path, &body);
+                }
+
+                req.set_body(body);
+
+                // set the user agent (required by the API)
+                req.headers_mut().set(UserAgent::new(USER_AGENT));
+
+                client
+                    .request(req)
+                    .and_then(|res| {
+                        let status = res.status();
+                        let cursors = parse_cursor(res.headers(), "CB-BEFORE").and_then(|before| {
+                            parse_cursor(res.headers(), "CB-AFTER").map(|after| (before, after))
+                        });
+                        res.body()
+                            .concat2()
+                            .map(move |body| (status, cursors, body))
+                    })
+                    .map_err(RestError::from)
+                    .and_then(move |(status, cursors, body)| {
+                        let (before, after) = cursors?;
+                        let response = HttpResponse {
+                            status,
+                            before,
+                            after,
+                            body,
+                        };
+                        if let Some(session) = session {
+                            session.record_exchange(&Exchange {
+                                status: response.status.as_u16(),
+                                before: response.before,
+                                after: response.after,
+                                response: String::from_utf8_lossy(&response.body).into_owned(),
+                                ..recorded_request
+                            })?;
+                        }
+                        check_status(response, path)
+                    })
+            });
 
         Box::new(work)
     }
+
+    /// Returns the recorded response of the request instead of sending it
+    fn replay(&self, session: &Session) -> Result<HttpResponse, RestError> {
+        let exchange = session.next_exchange(&self.http_method, &self.path)?;
+        let status = StatusCode::try_from(exchange.status)
+            .map_err(|_| RestError::SessionError(format!("Invalid status {}", exchange.status)))?;
+
+        check_status(
+            HttpResponse {
+                status,
+                before: exchange.before,
+                after: exchange.after,
+                body: Chunk::from(exchange.response.into_bytes()),
+            },
+            self.path.clone(),
+        )
+    }
+}
+
+/// Returns the response if its status is a success, the GDAX error otherwise
+fn check_status(response: HttpResponse, path: String) -> Result<HttpResponse, RestError> {
+    if response.status.is_success() {
+        Ok(response)
+    } else {
+        Err(RestError::ApiError(ApiError::from_body(
+            response.status,
+            &response.body,
+            path,
+        )))
+    }
+}
+
+fn sleep(duration: Duration, handle: &Handle) -> Box<Future<Item = (), Error = RestError>> {
+    if duration == Duration::new(0, 0) {
+        return Box::new(future::ok(()));
+    }
+
+    match Timeout::new(duration, handle) {
+        Ok(timeout) => Box::new(timeout.map_err(RestError::from)),
+        Err(io_error) => Box::new(future::err(RestError::from(io_error))),
+    }
+}
+
+struct HttpResponse {
+    status: StatusCode,
+    before: Option<usize>,
+    after: Option<usize>,
+    body: Chunk,
+}
+
+/// Returns the value of a pagination header, it is an error if the header is not a number
+fn parse_cursor(headers: &Headers, name: &str) -> Result<Option<usize>, RestError> {
+    match headers.get_raw(name).and_then(|raw| raw.one()) {
+        Some(value) => {
+            let value = String::from_utf8_lossy(value).into_owned();
+            match value.parse() {
+                Ok(cursor) => Ok(Some(cursor)),
+                Err(e) => Err(RestError::ParseError(ParseError::new(
+                    value,
+                    format!("Invalid {} header: {}", name, e),
+                ))),
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+fn parse_body<T: de::DeserializeOwned>(body: &[u8]) -> Result<T, RestError> {
+    serde_json::from_slice(body).map_err(|e| {
+        RestError::ParseError(ParseError::new(
+            String::from_utf8_lossy(body).into_owned(),
+            e.to_string(),
+        ))
+    })
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -123,7 +451,18 @@ pub struct Pagination {
     pub limit: Option<u32>,
 }
 
+/// A page of the results of a paginated end point with the cursors to request the adjacent
+/// pages, the cursors are `None` when there is no other page.
 #[derive(PartialEq, Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// The cursor to use with `Cursor::Before` to get the newer items
+    pub before: Option<usize>,
+    /// The cursor to use with `Cursor::After` to get the older items
+    pub after: Option<usize>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
 pub struct RestRequest {
     pub http_method: Method,
     pub route: Route,
@@ -135,20 +474,47 @@ pub struct RestRequest {
 /// request and parse the result.
 pub trait EndPointRequest<T: de::DeserializeOwned> {
     fn create_request(&self) -> RestRequest;
+
+    /// Returns `true` if the end point is private, the request is then signed with the client
+    /// credentials.
+    fn requires_authentication(&self) -> bool {
+        false
+    }
 }
 
-// TODO: test error handling!
+/// The end points whose results are split in pages (<https://docs.gdax.com/#pagination>), they
+/// can be walked with `RESTClient::paginate`.
+pub trait PaginatedRequest<T: de::DeserializeOwned>: EndPointRequest<Vec<T>> {}
+
 #[cfg(test)]
 mod tests {
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::path::PathBuf;
+    use std::process;
+    use std::time::{Duration, Instant};
+
+    use futures::{Future, Stream};
     use tokio_core::reactor::Core;
 
-    use mockito::{mock, SERVER_URL};
+    use mockito::{mock, Matcher, SERVER_URL};
     use hyper::Method;
 
-    use super::{EndPointRequest, RESTClient, RestRequest, Route};
+    use auth::Credentials;
+    use error::RestError;
+    use rate_limiter::RateLimiter;
+    use retry::RetryPolicy;
+    use session::Session;
+    use super::{Cursor, EndPointRequest, Page, PaginatedRequest, Pagination, RESTClient,
+                RestRequest, Route};
 
     struct FakeRequestHandler;
 
+    struct FakePrivateRequestHandler;
+
+    struct FakeRouteRequestHandler(&'static str);
+
     #[derive(Serialize, Deserialize, PartialEq, Debug)]
     struct FakeAnswerType {
         value: u64, // this value could be used to test
@@ -165,6 +531,32 @@ mod tests {
         }
     }
 
+    impl EndPointRequest<FakeAnswerType> for FakeRouteRequestHandler {
+        fn create_request(&self) -> RestRequest {
+            RestRequest {
+                http_method: Method::Get,
+                route: Route::new().add_segment(&self.0),
+                body: String::from(""),
+                pagination: None,
+            }
+        }
+    }
+
+    impl EndPointRequest<FakeAnswerType> for FakePrivateRequestHandler {
+        fn create_request(&self) -> RestRequest {
+            RestRequest {
+                http_method: Method::Get,
+                route: Route::new().add_segment(&"private"),
+                body: String::from(""),
+                pagination: None,
+            }
+        }
+
+        fn requires_authentication(&self) -> bool {
+            true
+        }
+    }
+
     #[test]
     fn test_fake_request() {
         let _m = mock("GET", "/test").with_body("{\"value\": 1}").create();
@@ -180,4 +572,382 @@ mod tests {
 
         assert_eq!(value.value, 1);
     }
+
+    #[test]
+    fn test_private_request() {
+        let _m = mock("GET", "/private")
+            .match_header("CB-ACCESS-KEY", "key")
+            .match_header("CB-ACCESS-SIGN", Matcher::Any)
+            .match_header("CB-ACCESS-TIMESTAMP", Matcher::Any)
+            .match_header("CB-ACCESS-PASSPHRASE", "passphrase")
+            .with_body("{\"value\": 2}")
+            .create();
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+
+        let credentials = Credentials::new("key", "c2VjcmV0", "passphrase").unwrap();
+        let mut test_client =
+            RESTClient::with_credentials(SERVER_URL, credentials, &handle).unwrap();
+
+        let value = core.run(test_client.send_request(&FakePrivateRequestHandler {}))
+            .unwrap();
+
+        assert_eq!(value.value, 2);
+    }
+
+    #[test]
+    fn test_private_request_without_credentials() {
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+
+        let mut test_client = RESTClient::new(SERVER_URL, &handle).unwrap();
+
+        match core.run(test_client.send_request(&FakePrivateRequestHandler {})) {
+            Err(RestError::AuthenticationRequired(route)) => assert_eq!(route, "/private"),
+            _ => panic!("The request should fail with AuthenticationRequired"),
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let _m = mock("GET", "/test_parse_error")
+            .with_body("{\"unexpected\": 1}")
+            .create();
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+
+        let mut test_client = RESTClient::new(SERVER_URL, &handle).unwrap();
+
+        match core.run(test_client.send_request(&FakeRouteRequestHandler("test_parse_error"))) {
+            Err(RestError::ParseError(parse_error)) => {
+                assert_eq!(parse_error.content(), "{\"unexpected\": 1}")
+            }
+            _ => panic!("The request should fail with ParseError"),
+        }
+    }
+
+    #[test]
+    fn test_uri_error() {
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+
+        let mut test_client = RESTClient::new("not an url", &handle).unwrap();
+
+        match core.run(test_client.send_request(&FakeRequestHandler {})) {
+            Err(RestError::UriError(_)) => (),
+            _ => panic!("The request should fail with UriError"),
+        }
+    }
+
+    #[test]
+    fn test_api_error() {
+        let _m = mock("GET", "/test_api_error")
+            .with_status(404)
+            .with_body("{\"message\": \"NotFound\"}")
+            .create();
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+
+        let mut test_client = RESTClient::new(SERVER_URL, &handle).unwrap();
+
+        match core.run(test_client.send_request(&FakeRouteRequestHandler("test_api_error"))) {
+            Err(RestError::ApiError(api_error)) => {
+                assert!(api_error.is_not_found());
+                assert_eq!(api_error.message(), "NotFound");
+                assert_eq!(api_error.route(), "/test_api_error");
+            }
+            _ => panic!("The request should fail with ApiError"),
+        }
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let _m = mock("GET", "/test_rate_limiter")
+            .with_body("{\"value\": 3}")
+            .expect(3)
+            .create();
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+
+        let mut test_client = RESTClient::new(SERVER_URL, &handle).unwrap();
+        test_client.set_rate_limiter(Some(RateLimiter::new(20.0, 1.0, 20.0, 1.0)));
+
+        let start = Instant::now();
+        let request = FakeRouteRequestHandler("test_rate_limiter");
+        let work = test_client
+            .send_request(&request)
+            .join3(test_client.send_request(&request), test_client.send_request(&request));
+        core.run(work).unwrap();
+
+        // the first request is sent immediately, the next ones wait 50ms each
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    struct FakePostRequestHandler;
+
+    impl EndPointRequest<FakeAnswerType> for FakePostRequestHandler {
+        fn create_request(&self) -> RestRequest {
+            RestRequest {
+                http_method: Method::Post,
+                route: Route::new().add_segment(&"test_no_retry"),
+                body: String::from("{}"),
+                pagination: None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_retry() {
+        let m = mock("GET", "/test_retry")
+            .with_status(503)
+            .with_body("{\"message\": \"Service Unavailable\"}")
+            .expect(3)
+            .create();
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+
+        let mut test_client = RESTClient::new(SERVER_URL, &handle).unwrap();
+        test_client.set_retry_policy(RetryPolicy::new(
+            3,
+            Duration::from_millis(10),
+            Duration::from_millis(20),
+        ));
+
+        match core.run(test_client.send_request(&FakeRouteRequestHandler("test_retry"))) {
+            Err(RestError::ApiError(api_error)) => assert!(api_error.is_server_error()),
+            _ => panic!("The request should fail with ApiError"),
+        }
+        m.assert();
+    }
+
+    #[test]
+    fn test_no_retry() {
+        let m = mock("POST", "/test_no_retry")
+            .with_status(500)
+            .with_body("{\"message\": \"Internal Server Error\"}")
+            .expect(1)
+            .create();
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+
+        let mut test_client = RESTClient::new(SERVER_URL, &handle).unwrap();
+        test_client.set_retry_policy(RetryPolicy::new(
+            3,
+            Duration::from_millis(10),
+            Duration::from_millis(20),
+        ));
+
+        assert!(core.run(test_client.send_request(&FakePostRequestHandler {})).is_err());
+        m.assert();
+    }
+
+    struct FakePaginatedRequestHandler(Option<Pagination>);
+
+    impl EndPointRequest<Vec<FakeAnswerType>> for FakePaginatedRequestHandler {
+        fn create_request(&self) -> RestRequest {
+            RestRequest {
+                http_method: Method::Get,
+                route: Route::new().add_segment(&"test_paginate"),
+                body: String::new(),
+                pagination: self.0,
+            }
+        }
+    }
+
+    impl PaginatedRequest<FakeAnswerType> for FakePaginatedRequestHandler {}
+
+    #[test]
+    fn test_paginated_request() {
+        let _m = mock("GET", "/test_paginated_request")
+            .with_header("CB-BEFORE", "12")
+            .with_header("CB-AFTER", "10")
+            .with_body("[{\"value\": 12}, {\"value\": 11}]")
+            .create();
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+
+        let mut test_client = RESTClient::new(SERVER_URL, &handle).unwrap();
+
+        struct Handler;
+        impl EndPointRequest<Vec<FakeAnswerType>> for Handler {
+            fn create_request(&self) -> RestRequest {
+                RestRequest {
+                    http_method: Method::Get,
+                    route: Route::new().add_segment(&"test_paginated_request"),
+                    body: String::new(),
+                    pagination: None,
+                }
+            }
+        }
+
+        let result = core.run(test_client.send_paginated_request(&Handler {}))
+            .unwrap();
+
+        let expected = Page {
+            items: vec![FakeAnswerType { value: 12 }, FakeAnswerType { value: 11 }],
+            before: Some(12),
+            after: Some(10),
+        };
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_paginate() {
+        let _m1 = mock("GET", "/test_paginate")
+            .with_header("CB-BEFORE", "5")
+            .with_header("CB-AFTER", "4")
+            .with_body("[{\"value\": 5}, {\"value\": 4}]")
+            .create();
+        let _m2 = mock("GET", "/test_paginate?after=4")
+            .with_header("CB-BEFORE", "3")
+            .with_header("CB-AFTER", "2")
+            .with_body("[{\"value\": 3}, {\"value\": 2}]")
+            .create();
+        let _m3 = mock("GET", "/test_paginate?after=2")
+            .with_header("CB-BEFORE", "1")
+            .with_header("CB-AFTER", "1")
+            .with_body("[{\"value\": 1}]")
+            .create();
+        let _m4 = mock("GET", "/test_paginate?after=1")
+            .with_body("[]")
+            .create();
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+
+        let mut test_client = RESTClient::new(SERVER_URL, &handle).unwrap();
+
+        let result = core.run(
+            test_client
+                .paginate(&FakePaginatedRequestHandler(None))
+                .collect(),
+        ).unwrap();
+
+        let expected: Vec<FakeAnswerType> = (1..6)
+            .rev()
+            .map(|value| FakeAnswerType { value })
+            .collect();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_paginate_invalid_cursor() {
+        let _m = mock("GET", "/test_paginate?before=7")
+            .with_header("CB-BEFORE", "8")
+            .with_header("CB-AFTER", "next")
+            .with_body("[{\"value\": 8}]")
+            .create();
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+
+        let mut test_client = RESTClient::new(SERVER_URL, &handle).unwrap();
+
+        let pagination = Pagination {
+            page: Cursor::Before(7),
+            limit: None,
+        };
+        match core.run(
+            test_client
+                .paginate(&FakePaginatedRequestHandler(Some(pagination)))
+                .collect(),
+        ) {
+            Err(RestError::ParseError(parse_error)) => assert_eq!(parse_error.content(), "next"),
+            _ => panic!("The request should fail with ParseError"),
+        }
+    }
+
+    #[test]
+    fn test_paginate_before() {
+        let _m1 = mock("GET", "/test_paginate?before=5&limit=1")
+            .with_header("CB-BEFORE", "6")
+            .with_body("[{\"value\": 6}]")
+            .create();
+        let _m2 = mock("GET", "/test_paginate?before=6&limit=1")
+            .with_body("[]")
+            .create();
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+
+        let mut test_client = RESTClient::new(SERVER_URL, &handle).unwrap();
+        let request = FakePaginatedRequestHandler(Some(Pagination {
+            page: Cursor::Before(5),
+            limit: Some(1),
+        }));
+
+        let result = core.run(test_client.paginate(&request).collect()).unwrap();
+
+        assert_eq!(result, vec![FakeAnswerType { value: 6 }]);
+    }
+
+    fn session_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("gdax_rs_{}_{}.jsonl", name, process::id()))
+    }
+
+    #[test]
+    fn test_record_and_replay_session() {
+        let _m1 = mock("GET", "/test_session")
+            .with_body("{\"value\": 7}")
+            .create();
+        let _m2 = mock("GET", "/test_session_not_found")
+            .with_status(404)
+            .with_body("{\"message\": \"NotFound\"}")
+            .create();
+        let path = session_path("rest_client_session");
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+
+        let mut recording_client = RESTClient::new(SERVER_URL, &handle).unwrap();
+        recording_client.set_session(Some(Session::record(&path).unwrap()));
+        let request = FakeRouteRequestHandler("test_session");
+        let value = core.run(recording_client.send_request(&request)).unwrap();
+        assert_eq!(value.value, 7);
+        assert!(
+            core.run(recording_client.send_request(&FakeRouteRequestHandler(
+                "test_session_not_found"
+            ))).is_err()
+        );
+
+        // nothing listens on this port, the responses come from the session file
+        let mut replaying_client = RESTClient::new("http://127.0.0.1:1", &handle).unwrap();
+        replaying_client.set_session(Some(Session::replay(&path).unwrap()));
+        let value = core.run(replaying_client.send_request(&request)).unwrap();
+        assert_eq!(value.value, 7);
+        match core.run(replaying_client.send_request(&FakeRouteRequestHandler(
+            "test_session_not_found",
+        ))) {
+            Err(RestError::ApiError(error)) => {
+                assert!(error.is_not_found());
+                assert_eq!(error.message(), "NotFound");
+            }
+            _ => panic!("The replayed request should fail with ApiError"),
+        }
+        match core.run(replaying_client.send_request(&request)) {
+            Err(RestError::SessionError(_)) => {}
+            _ => panic!("The session has no response left for this request"),
+        }
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_replay_private_request() {
+        let path = session_path("rest_client_private");
+        File::create(&path)
+            .unwrap()
+            .write_all(
+                b"{\"method\":\"GET\",\"route\":\"/private\",\"body\":\"\",\"status\":200,\
+                  \"before\":null,\"after\":null,\"response\":\"{\\\"value\\\": 3}\"}\n",
+            )
+            .unwrap();
+        let mut core = Core::new().unwrap();
+        let handle = core.handle();
+
+        // a replaying client does not need credentials
+        let mut test_client = RESTClient::new("http://127.0.0.1:1", &handle).unwrap();
+        test_client.set_session(Some(Session::replay(&path).unwrap()));
+        let value = core.run(test_client.send_request(&FakePrivateRequestHandler {}))
+            .unwrap();
+
+        assert_eq!(value.value, 3);
+        fs::remove_file(&path).unwrap();
+    }
 }
@@ -0,0 +1,123 @@
+use std::cmp;
+use std::time::Duration;
+
+use rand::{self, Rng};
+
+use error::RestError;
+
+/// The retry policy of a `RESTClient`, only the `GET` requests are retried because they are
+/// idempotent: an order placement or a cancellation is never sent twice.
+///
+/// A request is retried on connection errors, on GDAX server errors (5xx) and when it is rate
+/// limited (429). The delay before the retry `n` is `base_delay * 2^(n - 1)` capped at
+/// `max_delay`, with a random jitter between half the delay and the full delay.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Create a policy that sends a request at most `max_attempts` times.
+    pub fn new(max_attempts: u32, base_delay: Duration, max_delay: Duration) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: cmp::max(max_attempts, 1),
+            base_delay,
+            max_delay,
+        }
+    }
+
+    /// A policy that never retries
+    pub fn never() -> RetryPolicy {
+        RetryPolicy::new(1, Duration::new(0, 0), Duration::new(0, 0))
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns `true` if the request can be sent again after this error
+    pub fn is_retryable(error: &RestError) -> bool {
+        match *error {
+            RestError::RequestError(_) => true,
+            RestError::ApiError(ref api_error) => {
+                api_error.is_server_error() || api_error.is_rate_limited()
+            }
+            _ => false,
+        }
+    }
+
+    /// The delay to wait before the retry number `retry` (starting at 1)
+    pub fn delay(&self, retry: u32) -> Duration {
+        let max_delay = duration_to_millis(self.max_delay);
+        let exponent = cmp::min(retry.saturating_sub(1), 32);
+        let delay = cmp::min(
+            duration_to_millis(self.base_delay).saturating_mul(1 << exponent),
+            max_delay,
+        );
+
+        if delay == 0 {
+            return Duration::new(0, 0);
+        }
+        Duration::from_millis(rand::thread_rng().gen_range(delay / 2, delay + 1))
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, the first retry waits between 250ms and 500ms
+    fn default() -> RetryPolicy {
+        RetryPolicy::new(3, Duration::from_millis(500), Duration::from_secs(10))
+    }
+}
+
+fn duration_to_millis(duration: Duration) -> u64 {
+    duration.as_secs() * 1000 + u64::from(duration.subsec_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use hyper::StatusCode;
+
+    use error::{ApiError, RestError};
+    use super::RetryPolicy;
+
+    #[test]
+    fn test_delay() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100), Duration::from_millis(300));
+
+        for _ in 0..100 {
+            let first = policy.delay(1);
+            assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+            let second = policy.delay(2);
+            assert!(second >= Duration::from_millis(100) && second <= Duration::from_millis(200));
+            let capped = policy.delay(10);
+            assert!(capped >= Duration::from_millis(150) && capped <= Duration::from_millis(300));
+        }
+    }
+
+    #[test]
+    fn test_never() {
+        assert_eq!(RetryPolicy::never().max_attempts(), 1);
+        assert_eq!(RetryPolicy::never().delay(1), Duration::new(0, 0));
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        let api_error = |status| {
+            RestError::ApiError(ApiError::new(status, String::new(), String::from("/")))
+        };
+
+        assert!(RetryPolicy::is_retryable(&RestError::RequestError(
+            String::from("connection refused")
+        )));
+        assert!(RetryPolicy::is_retryable(&api_error(StatusCode::TooManyRequests)));
+        assert!(RetryPolicy::is_retryable(&api_error(StatusCode::BadGateway)));
+        assert!(!RetryPolicy::is_retryable(&api_error(StatusCode::BadRequest)));
+        assert!(!RetryPolicy::is_retryable(&RestError::AuthenticationRequired(
+            String::from("/")
+        )));
+    }
+}
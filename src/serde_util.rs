@@ -1,7 +1,7 @@
 use std::str::FromStr;
 use std::fmt::Display;
 
-use serde::de;
+use serde::{de, ser};
 
 /// Gdax return the floats values as strings, we need to use the `FromStr` trait to
 /// deserialize the string.
@@ -16,3 +16,40 @@ where
     let s: String = de::Deserialize::deserialize(deserializer)?;
     S::from_str(&s).map_err(de::Error::custom)
 }
+
+/// Same as `deserialize_from_str` for the fields that GDAX does not always return, it must be
+/// used with `#[serde(default)]` to accept a missing field.
+pub fn deserialize_option_from_str<'de, S, D>(deserializer: D) -> Result<Option<S>, D::Error>
+where
+    S: FromStr,
+    S::Err: Display,
+    D: de::Deserializer<'de>,
+{
+    let s: Option<String> = de::Deserialize::deserialize(deserializer)?;
+    match s {
+        Some(s) => S::from_str(&s).map(Some).map_err(de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+/// The counterpart of `deserialize_from_str`, the value is serialized as a string to produce the
+/// same json as GDAX.
+pub fn serialize_to_str<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: ser::Serializer,
+{
+    serializer.collect_str(value)
+}
+
+/// The counterpart of `deserialize_option_from_str`, a missing value is serialized as `null`.
+pub fn serialize_option_to_str<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Display,
+    S: ser::Serializer,
+{
+    match *value {
+        Some(ref value) => serializer.collect_str(value),
+        None => serializer.serialize_none(),
+    }
+}
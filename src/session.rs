@@ -0,0 +1,215 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use hyper::Method;
+use serde_json;
+
+use error::RestError;
+
+/// A recorded session of a `RESTClient`, it is used to reproduce the answers of GDAX offline.
+///
+/// In record mode, the route and the raw response of each request sent by the client are
+/// appended to the session file. In replay mode, the client sends no request: each request gets
+/// the next recorded response of the same method and route, the request body is not compared.
+///
+/// The file contains one json object per line so it can be read and edited by hand.
+#[derive(Clone)]
+pub struct Session {
+    mode: Mode,
+}
+
+#[derive(Clone)]
+enum Mode {
+    Record(Arc<Mutex<File>>),
+    Replay(Arc<Mutex<HashMap<String, VecDeque<Exchange>>>>),
+}
+
+/// A request and its response as they are saved in the session file
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub(crate) struct Exchange {
+    pub method: String,
+    pub route: String,
+    pub body: String,
+    pub status: u16,
+    pub before: Option<usize>,
+    pub after: Option<usize>,
+    pub response: String,
+}
+
+impl Session {
+    /// Create a session that records the exchanges in `path`, the file is replaced if it exists
+    pub fn record<P: AsRef<Path>>(path: P) -> Result<Session, RestError> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .map_err(|e| RestError::SessionError(e.to_string()))?;
+
+        Ok(Session {
+            mode: Mode::Record(Arc::new(Mutex::new(file))),
+        })
+    }
+
+    /// Create a session that replays the exchanges recorded in `path`
+    pub fn replay<P: AsRef<Path>>(path: P) -> Result<Session, RestError> {
+        let file = File::open(path).map_err(|e| RestError::SessionError(e.to_string()))?;
+
+        let mut exchanges = HashMap::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|e| RestError::SessionError(e.to_string()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let exchange: Exchange = serde_json::from_str(&line)
+                .map_err(|e| RestError::SessionError(format!("{} ({})", e, line)))?;
+            exchanges
+                .entry(key(&exchange.method, &exchange.route))
+                .or_insert_with(VecDeque::new)
+                .push_back(exchange);
+        }
+
+        Ok(Session {
+            mode: Mode::Replay(Arc::new(Mutex::new(exchanges))),
+        })
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        match self.mode {
+            Mode::Record(_) => false,
+            Mode::Replay(_) => true,
+        }
+    }
+
+    /// Save an exchange, it does nothing in replay mode
+    pub(crate) fn record_exchange(&self, exchange: &Exchange) -> Result<(), RestError> {
+        if let Mode::Record(ref file) = self.mode {
+            // an exchange is always serializable
+            let line = serde_json::to_string(exchange).unwrap();
+            let mut file = file.lock().unwrap();
+            writeln!(file, "{}", line)
+                .and_then(|_| file.flush())
+                .map_err(|e| RestError::SessionError(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the next recorded exchange of a request, it fails if there is none left or if
+    /// the session is not in replay mode.
+    pub(crate) fn next_exchange(
+        &self,
+        method: &Method,
+        route: &str,
+    ) -> Result<Exchange, RestError> {
+        let exchanges = match self.mode {
+            Mode::Replay(ref exchanges) => exchanges,
+            Mode::Record(_) => {
+                return Err(RestError::SessionError(String::from(
+                    "The session is not in replay mode",
+                )))
+            }
+        };
+
+        exchanges
+            .lock()
+            .unwrap()
+            .get_mut(&key(method.as_ref(), route))
+            .and_then(|exchanges| exchanges.pop_front())
+            .ok_or_else(|| {
+                RestError::SessionError(format!("No recorded response for {} {}", method, route))
+            })
+    }
+}
+
+fn key(method: &str, route: &str) -> String {
+    format!("{} {}", method, route)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    use hyper::Method;
+
+    use error::RestError;
+    use super::{Exchange, Session};
+
+    fn session_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("gdax_rs_{}_{}.jsonl", name, ::std::process::id()))
+    }
+
+    fn exchange(route: &str, response: &str) -> Exchange {
+        Exchange {
+            method: String::from("GET"),
+            route: String::from(route),
+            body: String::new(),
+            status: 200,
+            before: None,
+            after: Some(3),
+            response: String::from(response),
+        }
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let path = session_path("record_and_replay");
+
+        let session = Session::record(&path).unwrap();
+        assert!(!session.is_replaying());
+        session.record_exchange(&exchange("/time", "1")).unwrap();
+        session.record_exchange(&exchange("/products", "2")).unwrap();
+        session.record_exchange(&exchange("/time", "3")).unwrap();
+
+        let session = Session::replay(&path).unwrap();
+        assert!(session.is_replaying());
+        // the exchanges of each route are replayed in the recording order
+        assert_eq!(
+            session.next_exchange(&Method::Get, "/time").unwrap(),
+            exchange("/time", "1")
+        );
+        assert_eq!(
+            session.next_exchange(&Method::Get, "/time").unwrap(),
+            exchange("/time", "3")
+        );
+        assert_eq!(
+            session.next_exchange(&Method::Get, "/products").unwrap(),
+            exchange("/products", "2")
+        );
+
+        match session.next_exchange(&Method::Get, "/time") {
+            Err(RestError::SessionError(message)) => {
+                assert_eq!(message, "No recorded response for GET /time")
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+        match session.next_exchange(&Method::Post, "/products") {
+            Err(RestError::SessionError(_)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_replay_invalid_file() {
+        let path = session_path("invalid_file");
+        File::create(&path)
+            .unwrap()
+            .write_all(b"not json\n")
+            .unwrap();
+
+        match Session::replay(&path) {
+            Err(RestError::SessionError(message)) => assert!(message.contains("not json")),
+            _ => panic!("the session file should be invalid"),
+        }
+
+        fs::remove_file(&path).unwrap();
+        assert!(Session::replay(&path).is_err());
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use products::Side;
+
+/// An order resting on a simulated book, the market liquidity loaded from GDAX data has no id.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Resting {
+    pub id: Option<Uuid>,
+    pub size: Decimal,
+}
+
+/// The book of a product, it contains the market liquidity and the simulated limit orders.
+///
+/// The market liquidity of a price level is always ahead of the simulated orders in the queue.
+#[derive(Default, Debug)]
+pub struct Book {
+    bids: BTreeMap<Decimal, VecDeque<Resting>>,
+    asks: BTreeMap<Decimal, VecDeque<Resting>>,
+}
+
+impl Book {
+    fn levels(&self, side: Side) -> &BTreeMap<Decimal, VecDeque<Resting>> {
+        match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        }
+    }
+
+    fn levels_mut(&mut self, side: Side) -> &mut BTreeMap<Decimal, VecDeque<Resting>> {
+        match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        }
+    }
+
+    /// Replace the market size of a price level, a size of 0 removes the market liquidity
+    pub fn set_market_level(&mut self, side: Side, price: Decimal, size: Decimal) {
+        let levels = self.levels_mut(side);
+        let empty = {
+            let level = levels.entry(price).or_default();
+            level.retain(|resting| resting.id.is_some());
+            if !size.is_zero() {
+                level.push_front(Resting { id: None, size });
+            }
+            level.is_empty()
+        };
+        if empty {
+            levels.remove(&price);
+        }
+    }
+
+    /// Remove the market liquidity of both sides, the simulated orders are kept
+    pub fn clear_market(&mut self) {
+        for levels in &mut [&mut self.bids, &mut self.asks] {
+            for level in levels.values_mut() {
+                level.retain(|resting| resting.id.is_some());
+            }
+            levels.retain(|_, level| !level.is_empty());
+        }
+    }
+
+    /// Add a simulated order at the end of the queue of its price level
+    pub fn add_order(&mut self, side: Side, price: Decimal, id: Uuid, size: Decimal) {
+        self.levels_mut(side)
+            .entry(price)
+            .or_default()
+            .push_back(Resting { id: Some(id), size });
+    }
+
+    /// Remove a simulated order and returns its size
+    pub fn remove_order(&mut self, side: Side, price: Decimal, id: Uuid) -> Option<Decimal> {
+        let size = self.levels(side)
+            .get(&price)?
+            .iter()
+            .find(|resting| resting.id == Some(id))?
+            .size;
+        self.reduce_order(side, price, Some(id), size);
+        Some(size)
+    }
+
+    /// Decrease the size of an order (or of the market liquidity if `id` is `None`) and returns
+    /// its remaining size, the order is removed when it reaches 0.
+    pub fn reduce_order(
+        &mut self,
+        side: Side,
+        price: Decimal,
+        id: Option<Uuid>,
+        size: Decimal,
+    ) -> Option<Decimal> {
+        let levels = self.levels_mut(side);
+        let (remaining, empty) = {
+            let level = levels.get_mut(&price)?;
+            let index = level.iter().position(|resting| resting.id == id)?;
+            let remaining = level[index].size - size;
+            if remaining > Decimal::ZERO {
+                level[index].size = remaining;
+            } else {
+                level.remove(index);
+            }
+            (remaining, level.is_empty())
+        };
+        if empty {
+            levels.remove(&price);
+        }
+        Some(remaining)
+    }
+
+    /// The best price of a side: the highest bid or the lowest ask
+    pub fn best(&self, side: Side) -> Option<Decimal> {
+        match side {
+            Side::Buy => self.bids.keys().next_back().cloned(),
+            Side::Sell => self.asks.keys().next().cloned(),
+        }
+    }
+
+    /// The first order in the queue of a price level
+    pub fn front(&self, side: Side, price: Decimal) -> Option<&Resting> {
+        self.levels(side)
+            .get(&price)
+            .and_then(|level| level.front())
+    }
+
+    /// The market liquidity that a taker can match on a side without going beyond `limit`, the
+    /// simulated orders are left out because the self-trade prevention never fills them.
+    pub fn available(&self, side: Side, limit: Option<Decimal>) -> Decimal {
+        self.levels(side)
+            .iter()
+            .filter(|&(price, _)| match (side, limit) {
+                (_, None) => true,
+                (Side::Buy, Some(limit)) => *price >= limit,
+                (Side::Sell, Some(limit)) => *price <= limit,
+            })
+            .flat_map(|(_, level)| level.iter())
+            .filter(|resting| resting.id.is_none())
+            .fold(Decimal::ZERO, |total, resting| total + resting.size)
+    }
+
+    /// The simulated orders of a side that a trade at `price` reaches, the best prices first:
+    /// `(price, id, size)`.
+    pub fn orders_reached(&self, side: Side, price: Decimal) -> Vec<(Decimal, Uuid, Decimal)> {
+        let levels: Box<Iterator<Item = (&Decimal, &VecDeque<Resting>)>> = match side {
+            Side::Buy => Box::new(self.bids.range(price..).rev()),
+            Side::Sell => Box::new(self.asks.range(..=price)),
+        };
+        levels
+            .flat_map(|(price, level)| {
+                level.iter().filter_map(move |resting| {
+                    resting.id.map(|id| (*price, id, resting.size))
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use products::Side;
+    use super::{Book, Resting};
+
+    const ORDER: &str = "d0c5340b-6d6c-49d9-b567-48c4bfca13d2";
+
+    #[test]
+    fn test_market_liquidity_is_ahead() {
+        let id = Uuid::parse_str(ORDER).unwrap();
+        let mut book = Book::default();
+        book.add_order(Side::Sell, dec!(10), id, dec!(1));
+        book.set_market_level(Side::Sell, dec!(10), dec!(2));
+        book.set_market_level(Side::Sell, dec!(11), dec!(3));
+        book.set_market_level(Side::Buy, dec!(9), dec!(3));
+
+        assert_eq!(
+            book.front(Side::Sell, dec!(10)),
+            Some(&Resting {
+                id: None,
+                size: dec!(2),
+            })
+        );
+        assert_eq!(book.best(Side::Sell), Some(dec!(10)));
+        assert_eq!(book.best(Side::Buy), Some(dec!(9)));
+        assert_eq!(book.available(Side::Sell, Some(dec!(10))), dec!(2));
+        assert_eq!(book.available(Side::Sell, None), dec!(5));
+        assert_eq!(book.orders_reached(Side::Sell, dec!(10)), vec![(dec!(10), id, dec!(1))]);
+        assert!(book.orders_reached(Side::Sell, dec!(9.99)).is_empty());
+
+        book.clear_market();
+        assert_eq!(book.best(Side::Buy), None);
+        assert_eq!(book.available(Side::Sell, None), dec!(0));
+    }
+
+    #[test]
+    fn test_reduce_order() {
+        let id = Uuid::parse_str(ORDER).unwrap();
+        let mut book = Book::default();
+        book.add_order(Side::Buy, dec!(10), id, dec!(1));
+
+        assert_eq!(
+            book.reduce_order(Side::Buy, dec!(10), Some(id), dec!(0.25)),
+            Some(dec!(0.75))
+        );
+        assert_eq!(book.remove_order(Side::Buy, dec!(10), id), Some(dec!(0.75)));
+        assert_eq!(book.best(Side::Buy), None);
+        assert_eq!(book.remove_order(Side::Buy, dec!(10), id), None);
+    }
+}
@@ -0,0 +1,1048 @@
+use std::cmp::min;
+use std::collections::{BTreeMap, HashMap};
+use std::mem;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use hyper::StatusCode;
+use rust_decimal::{Decimal, RoundingStrategy};
+use uuid::Uuid;
+
+use error::{ApiError, RestError};
+use fills::{Fill, Liquidity};
+use orders::{CancelAfter, LimitOrder, MarketOrder, Order, OrderStatus, OrderType,
+             SelfTradePrevention, StopOrder, TimeInForce};
+use products::{OrderBook, PriceLevel, Product, Side, Trade};
+use websocket::{Done, DoneReason, L2Update, Snapshot};
+use super::book::Book;
+use super::fees::FeeRates;
+
+/// The precision of the sizes computed from the funds of a market order
+const SIZE_DECIMALS: u32 = 8;
+
+/// The messages that GDAX would send about the simulated orders.
+#[derive(PartialEq, Debug)]
+pub enum Event {
+    Fill(Fill),
+    Done(Done),
+}
+
+/// The part of an order which has not been matched yet.
+struct Taker {
+    id: Uuid,
+    side: Side,
+    limit: Option<Decimal>,
+    size: Option<Decimal>,
+    funds: Option<Decimal>,
+    stp: SelfTradePrevention,
+}
+
+impl Taker {
+    fn reaches(&self, price: Decimal) -> bool {
+        match (self.side, self.limit) {
+            (_, None) => true,
+            (Side::Buy, Some(limit)) => price <= limit,
+            (Side::Sell, Some(limit)) => price >= limit,
+        }
+    }
+
+    /// The size that the taker can still match at `price`
+    fn wanted(&self, price: Decimal) -> Decimal {
+        match (self.size, self.funds) {
+            (Some(size), _) => size,
+            (None, Some(funds)) => {
+                (funds / price).round_dp_with_strategy(SIZE_DECIMALS, RoundingStrategy::ToZero)
+            }
+            (None, None) => Decimal::ZERO,
+        }
+    }
+
+    fn consume(&mut self, price: Decimal, size: Decimal) {
+        if let Some(ref mut remaining) = self.size {
+            *remaining -= size;
+        }
+        if let Some(ref mut funds) = self.funds {
+            *funds -= price * size;
+        }
+    }
+}
+
+fn opposite(side: Side) -> Side {
+    match side {
+        Side::Buy => Side::Sell,
+        Side::Sell => Side::Buy,
+    }
+}
+
+fn bad_request(message: String) -> RestError {
+    RestError::ApiError(ApiError::new(
+        StatusCode::BadRequest,
+        message,
+        String::from("/orders"),
+    ))
+}
+
+/// A simulated GDAX exchange to paper trade against recorded market data.
+///
+/// The books are loaded with the market liquidity of `GetProductOrderBook` responses or of the
+/// `level2` channel, and the simulated orders are placed with the `LimitOrder`, `MarketOrder`
+/// and `StopOrder` builders. The orders taking liquidity are matched immediately against the
+/// books, the resting orders are filled by the trades given to `process_trade` (a trade fills
+/// the orders at a better price, and at the same price once the market liquidity ahead in the
+/// queue is consumed). The stop orders are triggered by these trades too.
+///
+/// The simulated fills and done messages are collected with `take_events`. The clock of the
+/// exchange starts at the Unix epoch, it follows the time of the trades or `advance_time`.
+pub struct SimulatedExchange {
+    products: HashMap<String, Product>,
+    books: HashMap<String, Book>,
+    orders: HashMap<Uuid, Order>,
+    expirations: Vec<(DateTime<Utc>, Uuid)>,
+    stops: Vec<Uuid>,
+    last_prices: HashMap<String, Decimal>,
+    time: DateTime<Utc>,
+    sequence: usize,
+    trade_id: usize,
+    order_count: u64,
+    fees: FeeRates,
+    events: Vec<Event>,
+}
+
+impl SimulatedExchange {
+    /// Create an exchange trading `products` with empty books and the default `FeeRates`
+    pub fn new(products: Vec<Product>) -> SimulatedExchange {
+        SimulatedExchange {
+            products: products
+                .into_iter()
+                .map(|product| (product.id.clone(), product))
+                .collect(),
+            books: HashMap::new(),
+            orders: HashMap::new(),
+            expirations: Vec::new(),
+            stops: Vec::new(),
+            last_prices: HashMap::new(),
+            time: Utc.timestamp(0, 0),
+            sequence: 0,
+            trade_id: 0,
+            order_count: 0,
+            fees: FeeRates::default(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Set the rates charged to the maker and taker fills of the simulated orders
+    pub fn set_fee_rates(&mut self, maker_fee: Decimal, taker_fee: Decimal) {
+        self.fees = FeeRates::new(maker_fee, taker_fee);
+    }
+
+    /// The current time of the exchange
+    pub fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+
+    /// Move the clock forward, the `GoodTillTime` orders expired at `time` are canceled.
+    pub fn advance_time(&mut self, time: DateTime<Utc>) {
+        if time > self.time {
+            self.time = time;
+        }
+        let now = self.time;
+        let (expired, pending) = self.expirations
+            .drain(..)
+            .partition(|&(expiration, _)| expiration <= now);
+        self.expirations = pending;
+        for (_, id) in expired {
+            if self.is_open(id) {
+                self.cancel_open(id);
+            }
+        }
+    }
+
+    /// Replace the market liquidity of a product with a response of `GetProductOrderBook`, the
+    /// orders of a level 3 book are aggregated by price.
+    pub fn load_order_book(&mut self, product_id: &str, order_book: &OrderBook<PriceLevel>) {
+        let aggregate = |levels: &[PriceLevel]| {
+            let mut sizes = BTreeMap::new();
+            for level in levels {
+                *sizes.entry(level.price).or_insert(Decimal::ZERO) += level.size;
+            }
+            sizes
+        };
+        let bids = aggregate(&order_book.bids);
+        let asks = aggregate(&order_book.asks);
+        self.load_levels(product_id, bids, asks);
+    }
+
+    /// Replace the market liquidity of a product with the snapshot of the `level2` channel
+    pub fn load_snapshot(&mut self, snapshot: &Snapshot) {
+        let bids = snapshot
+            .bids
+            .iter()
+            .map(|level| (level.price, level.size))
+            .collect();
+        let asks = snapshot
+            .asks
+            .iter()
+            .map(|level| (level.price, level.size))
+            .collect();
+        self.load_levels(&snapshot.product_id, bids, asks);
+    }
+
+    fn load_levels(
+        &mut self,
+        product_id: &str,
+        bids: BTreeMap<Decimal, Decimal>,
+        asks: BTreeMap<Decimal, Decimal>,
+    ) {
+        let book = self.book(product_id);
+        book.clear_market();
+        for (price, size) in bids {
+            book.set_market_level(Side::Buy, price, size);
+        }
+        for (price, size) in asks {
+            book.set_market_level(Side::Sell, price, size);
+        }
+    }
+
+    /// Apply an update of the `level2` channel to the market liquidity
+    pub fn apply_l2_update(&mut self, update: &L2Update) {
+        if let Some(time) = update.time {
+            self.advance_time(time);
+        }
+        let book = self.book(&update.product_id);
+        for change in &update.changes {
+            book.set_market_level(change.side, change.price, change.size);
+        }
+    }
+
+    /// Replay a trade of the market, the trades must be given in chronological order.
+    ///
+    /// The side of a trade is the side of its maker, so a `Side::Buy` trade fills the resting
+    /// bids. The stop orders reached by the trade price are then triggered.
+    pub fn process_trade(&mut self, product_id: &str, trade: &Trade) {
+        self.advance_time(trade.time);
+        let maker_side = trade.side;
+        let mut remaining = trade.size;
+
+        // the market liquidity at the trade price was in the queue before the simulated orders
+        let ahead = self.book(product_id)
+            .front(maker_side, trade.price)
+            .and_then(|resting| match resting.id {
+                None => Some(resting.size),
+                Some(_) => None,
+            });
+        if let Some(ahead) = ahead {
+            let size = min(ahead, remaining);
+            self.book(product_id)
+                .reduce_order(maker_side, trade.price, None, size);
+            remaining -= size;
+        }
+
+        let reached = self.book(product_id).orders_reached(maker_side, trade.price);
+        for (price, id, size) in reached {
+            if remaining.is_zero() {
+                break;
+            }
+            let size = min(size, remaining);
+            remaining -= size;
+            self.fill(id, price, size, Liquidity::Maker);
+            let left = self.book(product_id)
+                .reduce_order(maker_side, price, Some(id), size);
+            if left == Some(Decimal::ZERO) {
+                self.done(id, DoneReason::Filled, Some(Decimal::ZERO));
+            }
+        }
+
+        self.last_prices.insert(product_id.to_owned(), trade.price);
+        self.trigger_stops(product_id, trade.price);
+    }
+
+    /// Place a limit order, it is matched against the book and the rest is handled according
+    /// to its time in force.
+    pub fn place_limit_order(&mut self, order: &LimitOrder) -> Result<Order, RestError> {
+        let post_only = {
+            let product = self.product(&order.product_id)?;
+            SimulatedExchange::check_price(product, order.price)?;
+            SimulatedExchange::check_size(product, order.size)?;
+            order.post_only.unwrap_or(false) || product.post_only
+        };
+        let time_in_force = order.time_in_force.unwrap_or(TimeInForce::GoodTillCanceled);
+        let stp = order.stp.unwrap_or(SelfTradePrevention::DecreaseAndCancel);
+        let maker_side = opposite(order.side);
+        let crosses = {
+            let book = self.book(&order.product_id);
+            match (order.side, book.best(maker_side)) {
+                (Side::Buy, Some(ask)) => ask <= order.price,
+                (Side::Sell, Some(bid)) => bid >= order.price,
+                (_, None) => false,
+            }
+        };
+        if post_only && crosses {
+            return Err(bad_request(String::from("Post only order would take liquidity")));
+        }
+
+        let id = self.create_order(&order.product_id, order.side, OrderType::Limit, stp);
+        {
+            let created = self.orders.get_mut(&id).unwrap();
+            created.price = Some(order.price);
+            created.size = Some(order.size);
+            created.time_in_force = Some(time_in_force);
+            created.post_only = post_only;
+        }
+
+        if time_in_force == TimeInForce::FillOrKill
+            && self.book(&order.product_id)
+                .available(maker_side, Some(order.price)) < order.size
+        {
+            self.done(id, DoneReason::Canceled, Some(order.size));
+            return Ok(self.orders[&id].clone());
+        }
+
+        let mut taker = Taker {
+            id,
+            side: order.side,
+            limit: Some(order.price),
+            size: Some(order.size),
+            funds: None,
+            stp,
+        };
+        let canceled = self.take(&order.product_id, &mut taker);
+        let remaining = taker.size.unwrap_or(Decimal::ZERO);
+
+        if canceled {
+            self.done(id, DoneReason::Canceled, Some(remaining));
+        } else if remaining.is_zero() {
+            self.done(id, DoneReason::Filled, Some(remaining));
+        } else {
+            match time_in_force {
+                TimeInForce::ImmediateOrCancel | TimeInForce::FillOrKill => {
+                    self.done(id, DoneReason::Canceled, Some(remaining));
+                }
+                TimeInForce::GoodTillCanceled | TimeInForce::GoodTillTime => {
+                    self.book(&order.product_id)
+                        .add_order(order.side, order.price, id, remaining);
+                    self.orders.get_mut(&id).unwrap().status = OrderStatus::Open;
+                    if let Some(cancel_after) = order.cancel_after {
+                        let duration = match cancel_after {
+                            CancelAfter::Minute => Duration::minutes(1),
+                            CancelAfter::Hour => Duration::hours(1),
+                            CancelAfter::Day => Duration::days(1),
+                        };
+                        self.expirations.push((self.time + duration, id));
+                    }
+                }
+            }
+        }
+        Ok(self.orders[&id].clone())
+    }
+
+    /// Place a market order, it is matched immediately against the book.
+    pub fn place_market_order(&mut self, order: &MarketOrder) -> Result<Order, RestError> {
+        self.check_taker(&order.product_id, order.size, order.funds)?;
+        let stp = order.stp.unwrap_or(SelfTradePrevention::DecreaseAndCancel);
+        let funds = order.funds.map(|funds| self.funds_without_fee(funds));
+        let id = self.create_order(&order.product_id, order.side, OrderType::Market, stp);
+        {
+            let created = self.orders.get_mut(&id).unwrap();
+            created.size = order.size;
+            created.funds = funds;
+            created.specified_funds = order.funds;
+        }
+        self.execute_market(id);
+        Ok(self.orders[&id].clone())
+    }
+
+    /// Place a stop order, it stays active until a trade reaches the stop price: at or below
+    /// it for a sell and at or above it for a buy. Then it is executed as a market order.
+    pub fn place_stop_order(&mut self, order: &StopOrder) -> Result<Order, RestError> {
+        self.check_taker(&order.product_id, order.size, order.funds)?;
+        SimulatedExchange::check_price(self.product(&order.product_id)?, order.stop_price)?;
+        let stp = order.stp.unwrap_or(SelfTradePrevention::DecreaseAndCancel);
+        let funds = order.funds.map(|funds| self.funds_without_fee(funds));
+        let id = self.create_order(&order.product_id, order.side, OrderType::Stop, stp);
+        {
+            let created = self.orders.get_mut(&id).unwrap();
+            created.price = Some(order.stop_price);
+            created.size = order.size;
+            created.funds = funds;
+            created.specified_funds = order.funds;
+            created.status = OrderStatus::Active;
+        }
+        self.stops.push(id);
+        Ok(self.orders[&id].clone())
+    }
+
+    /// Cancel an open limit order or an active stop order
+    pub fn cancel_order(&mut self, id: Uuid) -> Result<(), RestError> {
+        let status = self.orders.get(&id).map(|order| order.status);
+        match status {
+            Some(OrderStatus::Open) => {
+                self.cancel_open(id);
+                Ok(())
+            }
+            Some(OrderStatus::Active) => {
+                self.stops.retain(|stop| *stop != id);
+                self.done(id, DoneReason::Canceled, None);
+                Ok(())
+            }
+            _ => Err(RestError::ApiError(ApiError::new(
+                StatusCode::NotFound,
+                String::from("NotFound"),
+                format!("/orders/{}", id),
+            ))),
+        }
+    }
+
+    /// The state of a simulated order
+    pub fn order(&self, id: Uuid) -> Option<&Order> {
+        self.orders.get(&id)
+    }
+
+    /// Returns the events produced since the last call
+    pub fn take_events(&mut self) -> Vec<Event> {
+        mem::replace(&mut self.events, Vec::new())
+    }
+
+    /// The best bid of a product, including the simulated orders
+    pub fn best_bid(&self, product_id: &str) -> Option<Decimal> {
+        self.books
+            .get(product_id)
+            .and_then(|book| book.best(Side::Buy))
+    }
+
+    /// The best ask of a product, including the simulated orders
+    pub fn best_ask(&self, product_id: &str) -> Option<Decimal> {
+        self.books
+            .get(product_id)
+            .and_then(|book| book.best(Side::Sell))
+    }
+
+    /// The price of the last trade given to `process_trade`
+    pub fn last_price(&self, product_id: &str) -> Option<Decimal> {
+        self.last_prices.get(product_id).cloned()
+    }
+
+    fn book(&mut self, product_id: &str) -> &mut Book {
+        self.books
+            .entry(product_id.to_owned())
+            .or_default()
+    }
+
+    /// Returns the product if it accepts new orders
+    fn product(&self, product_id: &str) -> Result<&Product, RestError> {
+        let product = self.products
+            .get(product_id)
+            .ok_or_else(|| bad_request(String::from("Product not found")))?;
+        if product.cancel_only {
+            return Err(bad_request(String::from("Product is in cancel only mode")));
+        }
+        Ok(product)
+    }
+
+    fn check_price(product: &Product, price: Decimal) -> Result<(), RestError> {
+        if price <= Decimal::ZERO {
+            return Err(bad_request(String::from("Invalid price")));
+        }
+        // a product without increment accepts any price
+        if !product.quote_increment.is_zero() && !(price % product.quote_increment).is_zero() {
+            return Err(bad_request(format!(
+                "price is too accurate. Smallest unit is {}",
+                product.quote_increment
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_size(product: &Product, size: Decimal) -> Result<(), RestError> {
+        if size < product.base_min_size {
+            return Err(bad_request(format!(
+                "size is too small. Minimum size is {}",
+                product.base_min_size
+            )));
+        }
+        if size > product.base_max_size {
+            return Err(bad_request(format!(
+                "size is too large. Maximum size is {}",
+                product.base_max_size
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check the amount of a market or stop order
+    fn check_taker(
+        &self,
+        product_id: &str,
+        size: Option<Decimal>,
+        funds: Option<Decimal>,
+    ) -> Result<(), RestError> {
+        let product = self.product(product_id)?;
+        if product.limit_only {
+            return Err(bad_request(String::from("Product is in limit only mode")));
+        }
+        if product.post_only {
+            return Err(bad_request(String::from("Product is in post only mode")));
+        }
+        if let Some(size) = size {
+            SimulatedExchange::check_size(product, size)?;
+        }
+        if let Some(funds) = funds {
+            if funds <= Decimal::ZERO
+                || product.min_market_funds.map_or(false, |min| funds < min)
+            {
+                return Err(bad_request(format!(
+                    "funds is too small. Minimum size is {}",
+                    product.min_market_funds.unwrap_or(Decimal::ZERO)
+                )));
+            }
+            if let Some(max) = product.max_market_funds {
+                if funds > max {
+                    return Err(bad_request(format!(
+                        "funds is too large. Maximum size is {}",
+                        max
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The part of the specified funds which can be spent, GDAX holds the taker fee in them:
+    /// funds of 10 with a fee of 0.25% leave 9.97506234 to buy.
+    fn funds_without_fee(&self, specified_funds: Decimal) -> Decimal {
+        (specified_funds / (Decimal::ONE + self.fees.taker))
+            .round_dp_with_strategy(SIZE_DECIMALS, RoundingStrategy::ToZero)
+    }
+
+    /// Create a pending order with a deterministic id
+    fn create_order(
+        &mut self,
+        product_id: &str,
+        side: Side,
+        order_type: OrderType,
+        stp: SelfTradePrevention,
+    ) -> Uuid {
+        self.order_count += 1;
+        let mut bytes = [0u8; 16];
+        for (index, byte) in bytes[8..].iter_mut().enumerate() {
+            *byte = (self.order_count >> (8 * (7 - index))) as u8;
+        }
+        let id = Uuid::from_bytes(&bytes).unwrap();
+
+        self.orders.insert(
+            id,
+            Order {
+                id,
+                price: None,
+                size: None,
+                funds: None,
+                specified_funds: None,
+                product_id: product_id.to_owned(),
+                side,
+                stp: Some(stp),
+                order_type,
+                time_in_force: None,
+                post_only: false,
+                created_at: self.time,
+                done_at: None,
+                done_reason: None,
+                fill_fees: Decimal::ZERO,
+                filled_size: Decimal::ZERO,
+                executed_value: Decimal::ZERO,
+                status: OrderStatus::Pending,
+                settled: false,
+            },
+        );
+        id
+    }
+
+    /// Match a market order (or a triggered stop order) and mark it as done
+    fn execute_market(&mut self, id: Uuid) {
+        let (product_id, mut taker) = {
+            let order = &self.orders[&id];
+            let taker = Taker {
+                id,
+                side: order.side,
+                limit: None,
+                size: order.size,
+                funds: order.funds,
+                stp: order.stp.unwrap_or(SelfTradePrevention::DecreaseAndCancel),
+            };
+            (order.product_id.clone(), taker)
+        };
+        let canceled = self.take(&product_id, &mut taker);
+        let reason = if canceled || self.orders[&id].filled_size.is_zero() {
+            DoneReason::Canceled
+        } else {
+            DoneReason::Filled
+        };
+        self.done(id, reason, None);
+    }
+
+    fn trigger_stops(&mut self, product_id: &str, price: Decimal) {
+        let triggered: Vec<Uuid> = self.stops
+            .iter()
+            .cloned()
+            .filter(|id| {
+                let order = &self.orders[id];
+                order.product_id == product_id && match (order.side, order.price) {
+                    (Side::Sell, Some(stop_price)) => price <= stop_price,
+                    (Side::Buy, Some(stop_price)) => price >= stop_price,
+                    (_, None) => false,
+                }
+            })
+            .collect();
+        for id in triggered {
+            self.stops.retain(|stop| *stop != id);
+            self.orders.get_mut(&id).unwrap().status = OrderStatus::Pending;
+            self.execute_market(id);
+        }
+    }
+
+    /// Match a taker against the opposite side of the book, returns `true` if the taker has
+    /// been canceled by the self-trade prevention.
+    fn take(&mut self, product_id: &str, taker: &mut Taker) -> bool {
+        let maker_side = opposite(taker.side);
+        loop {
+            let price = match self.book(product_id).best(maker_side) {
+                Some(price) if taker.reaches(price) => price,
+                _ => return false,
+            };
+            let wanted = taker.wanted(price);
+            if wanted <= Decimal::ZERO {
+                return false;
+            }
+            let resting = match self.book(product_id).front(maker_side, price) {
+                Some(resting) => resting.clone(),
+                None => return false,
+            };
+
+            match resting.id {
+                None => {
+                    let size = min(resting.size, wanted);
+                    self.book(product_id)
+                        .reduce_order(maker_side, price, None, size);
+                    self.fill(taker.id, price, size, Liquidity::Taker);
+                    taker.consume(price, size);
+                }
+                Some(maker_id) => match taker.stp {
+                    SelfTradePrevention::DecreaseAndCancel => {
+                        if resting.size > wanted {
+                            self.book(product_id)
+                                .reduce_order(maker_side, price, Some(maker_id), wanted);
+                            let maker = self.orders.get_mut(&maker_id).unwrap();
+                            maker.size = maker.size.map(|size| size - wanted);
+                            return true;
+                        }
+                        self.cancel_open(maker_id);
+                        if resting.size == wanted {
+                            return true;
+                        }
+                        taker.consume(price, resting.size);
+                    }
+                    SelfTradePrevention::CancelOldest => self.cancel_open(maker_id),
+                    SelfTradePrevention::CancelNewest => return true,
+                    SelfTradePrevention::CancelBoth => {
+                        self.cancel_open(maker_id);
+                        return true;
+                    }
+                },
+            }
+        }
+    }
+
+    fn fill(&mut self, id: Uuid, price: Decimal, size: Decimal, liquidity: Liquidity) {
+        let fee = self.fees.fee(price, size, liquidity);
+        self.trade_id += 1;
+
+        let order = self.orders.get_mut(&id).unwrap();
+        order.filled_size += size;
+        order.executed_value += price * size;
+        order.fill_fees += fee;
+
+        self.events.push(Event::Fill(Fill {
+            trade_id: self.trade_id,
+            product_id: order.product_id.clone(),
+            price,
+            size,
+            order_id: id,
+            created_at: self.time,
+            liquidity,
+            fee,
+            settled: true,
+            side: order.side,
+        }));
+    }
+
+    fn is_open(&self, id: Uuid) -> bool {
+        self.orders.get(&id).map(|order| order.status) == Some(OrderStatus::Open)
+    }
+
+    /// Remove an open order from the book and mark it as canceled
+    fn cancel_open(&mut self, id: Uuid) {
+        let (product_id, side, price) = {
+            let order = &self.orders[&id];
+            (order.product_id.clone(), order.side, order.price.unwrap())
+        };
+        let remaining = self.book(&product_id).remove_order(side, price, id);
+        self.done(id, DoneReason::Canceled, remaining);
+    }
+
+    fn done(&mut self, id: Uuid, reason: DoneReason, remaining_size: Option<Decimal>) {
+        self.sequence += 1;
+        let order = self.orders.get_mut(&id).unwrap();
+        order.status = OrderStatus::Done;
+        order.done_at = Some(self.time);
+        order.done_reason = Some(String::from(match reason {
+            DoneReason::Filled => "filled",
+            DoneReason::Canceled => "canceled",
+        }));
+        order.settled = true;
+
+        self.events.push(Event::Done(Done {
+            time: self.time,
+            product_id: order.product_id.clone(),
+            sequence: self.sequence,
+            price: order.price,
+            order_id: id,
+            reason,
+            side: order.side,
+            remaining_size,
+            user_id: None,
+            profile_id: None,
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use rust_decimal::Decimal;
+
+    use error::RestError;
+    use fills::Liquidity;
+    use orders::{CancelAfter, LimitOrder, MarketOrder, OrderStatus, SelfTradePrevention,
+                 StopOrder};
+    use products::{Product, Side, Trade};
+    use websocket::{DoneReason, Snapshot, SnapshotLevel};
+    use super::{Event, SimulatedExchange};
+
+    fn product() -> Product {
+        Product {
+            id: String::from("BTC-USD"),
+            base_currency: String::from("BTC"),
+            quote_currency: String::from("USD"),
+            base_min_size: dec!(0.01),
+            base_max_size: dec!(100),
+            quote_increment: dec!(0.01),
+            display_name: String::from("BTC/USD"),
+            status: String::from("online"),
+            status_message: None,
+            min_market_funds: Some(dec!(10)),
+            max_market_funds: Some(dec!(1000000)),
+            post_only: false,
+            limit_only: false,
+            cancel_only: false,
+        }
+    }
+
+    fn level(price: Decimal, size: Decimal) -> SnapshotLevel {
+        SnapshotLevel { price, size }
+    }
+
+    /// An exchange without fees, the market has bids at 99 and 98, and asks at 101 and 102
+    fn exchange() -> SimulatedExchange {
+        let mut exchange = SimulatedExchange::new(vec![product()]);
+        exchange.set_fee_rates(dec!(0), dec!(0));
+        exchange.load_snapshot(&Snapshot {
+            product_id: String::from("BTC-USD"),
+            bids: vec![level(dec!(99), dec!(1)), level(dec!(98), dec!(2))],
+            asks: vec![level(dec!(101), dec!(1)), level(dec!(102), dec!(2))],
+        });
+        exchange
+    }
+
+    fn limit(side: Side, price: Decimal, size: Decimal) -> LimitOrder {
+        LimitOrder::new(String::from("BTC-USD"), side, price, size)
+    }
+
+    fn trade(side: Side, price: Decimal, size: Decimal) -> Trade {
+        Trade {
+            time: Utc.ymd(2017, 12, 21).and_hms(10, 0, 0),
+            trade_id: 1,
+            price,
+            size,
+            side,
+        }
+    }
+
+    /// The fills of the events as `(price, size, liquidity)`
+    fn fills(events: &[Event]) -> Vec<(Decimal, Decimal, Liquidity)> {
+        events
+            .iter()
+            .filter_map(|event| match *event {
+                Event::Fill(ref fill) => Some((fill.price, fill.size, fill.liquidity)),
+                Event::Done(_) => None,
+            })
+            .collect()
+    }
+
+    /// The done messages of the events as `(reason, remaining_size)`
+    fn dones(events: &[Event]) -> Vec<(DoneReason, Option<Decimal>)> {
+        events
+            .iter()
+            .filter_map(|event| match *event {
+                Event::Done(ref done) => Some((done.reason, done.remaining_size)),
+                Event::Fill(_) => None,
+            })
+            .collect()
+    }
+
+    fn error_message(error: RestError) -> String {
+        match error {
+            RestError::ApiError(error) => error.message().to_owned(),
+            error => panic!("Unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn test_validation() {
+        let mut exchange = exchange();
+
+        let result = exchange.place_limit_order(&limit(Side::Buy, dec!(100.001), dec!(1)));
+        assert_eq!(
+            error_message(result.unwrap_err()),
+            "price is too accurate. Smallest unit is 0.01"
+        );
+        let result = exchange.place_limit_order(&limit(Side::Buy, dec!(100), dec!(0.001)));
+        assert_eq!(
+            error_message(result.unwrap_err()),
+            "size is too small. Minimum size is 0.01"
+        );
+        let order = MarketOrder::with_funds(String::from("BTC-USD"), Side::Buy, dec!(5));
+        let result = exchange.place_market_order(&order);
+        assert_eq!(
+            error_message(result.unwrap_err()),
+            "funds is too small. Minimum size is 10"
+        );
+        let order = limit(Side::Buy, dec!(101), dec!(1)).good_till_canceled(true);
+        let result = exchange.place_limit_order(&order);
+        assert_eq!(
+            error_message(result.unwrap_err()),
+            "Post only order would take liquidity"
+        );
+
+        let mut limit_only = product();
+        limit_only.limit_only = true;
+        let mut exchange = SimulatedExchange::new(vec![limit_only]);
+        let order = MarketOrder::with_size(String::from("BTC-USD"), Side::Buy, dec!(1));
+        let result = exchange.place_market_order(&order);
+        assert_eq!(
+            error_message(result.unwrap_err()),
+            "Product is in limit only mode"
+        );
+        assert!(exchange.take_events().is_empty());
+
+        let mut any_price = product();
+        any_price.quote_increment = dec!(0);
+        let mut exchange = SimulatedExchange::new(vec![any_price]);
+        assert!(exchange
+            .place_limit_order(&limit(Side::Buy, dec!(100.001), dec!(1)))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_limit_order() {
+        let mut exchange = exchange();
+        exchange.set_fee_rates(dec!(0), dec!(0.003));
+
+        let order = exchange
+            .place_limit_order(&limit(Side::Buy, dec!(101.5), dec!(1.5)))
+            .unwrap();
+        let events = exchange.take_events();
+
+        assert_eq!(fills(&events), vec![(dec!(101), dec!(1), Liquidity::Taker)]);
+        assert!(dones(&events).is_empty());
+        assert_eq!(order.status, OrderStatus::Open);
+        assert_eq!(order.filled_size, dec!(1));
+        assert_eq!(order.fill_fees, dec!(0.303));
+        assert_eq!(exchange.best_bid("BTC-USD"), Some(dec!(101.5)));
+        assert_eq!(exchange.best_ask("BTC-USD"), Some(dec!(102)));
+
+        exchange.process_trade("BTC-USD", &trade(Side::Buy, dec!(101.5), dec!(2)));
+        let events = exchange.take_events();
+
+        assert_eq!(fills(&events), vec![(dec!(101.5), dec!(0.5), Liquidity::Maker)]);
+        assert_eq!(dones(&events), vec![(DoneReason::Filled, Some(dec!(0)))]);
+        let order = exchange.order(order.id).unwrap();
+        assert_eq!(order.status, OrderStatus::Done);
+        assert_eq!(order.executed_value, dec!(151.75));
+        assert_eq!(exchange.last_price("BTC-USD"), Some(dec!(101.5)));
+    }
+
+    #[test]
+    fn test_queue_position() {
+        let mut exchange = exchange();
+        exchange
+            .place_limit_order(&limit(Side::Buy, dec!(99), dec!(1)))
+            .unwrap();
+
+        // the market bid at 99 is ahead of the simulated order
+        exchange.process_trade("BTC-USD", &trade(Side::Buy, dec!(99), dec!(1)));
+        assert!(exchange.take_events().is_empty());
+
+        exchange.process_trade("BTC-USD", &trade(Side::Buy, dec!(99), dec!(0.25)));
+        let events = exchange.take_events();
+        assert_eq!(fills(&events), vec![(dec!(99), dec!(0.25), Liquidity::Maker)]);
+    }
+
+    #[test]
+    fn test_time_in_force() {
+        let mut exchange = exchange();
+
+        let order = limit(Side::Buy, dec!(101), dec!(2)).immediate_or_cancel();
+        exchange.place_limit_order(&order).unwrap();
+        let events = exchange.take_events();
+        assert_eq!(fills(&events), vec![(dec!(101), dec!(1), Liquidity::Taker)]);
+        assert_eq!(dones(&events), vec![(DoneReason::Canceled, Some(dec!(1)))]);
+
+        let order = limit(Side::Sell, dec!(98), dec!(4)).fill_or_kill();
+        let order = exchange.place_limit_order(&order).unwrap();
+        let events = exchange.take_events();
+        assert!(fills(&events).is_empty());
+        assert_eq!(dones(&events), vec![(DoneReason::Canceled, Some(dec!(4)))]);
+        assert_eq!(order.done_reason, Some(String::from("canceled")));
+
+        // the simulated ask at 101.5 cannot fill the rest because of the self-trade prevention
+        let resting = exchange
+            .place_limit_order(&limit(Side::Sell, dec!(101.5), dec!(1)))
+            .unwrap();
+        let order = limit(Side::Buy, dec!(102), dec!(3)).fill_or_kill();
+        exchange.place_limit_order(&order).unwrap();
+        let events = exchange.take_events();
+        assert!(fills(&events).is_empty());
+        assert_eq!(dones(&events), vec![(DoneReason::Canceled, Some(dec!(3)))]);
+        assert_eq!(exchange.order(resting.id).unwrap().status, OrderStatus::Open);
+        exchange.cancel_order(resting.id).unwrap();
+
+        let order = limit(Side::Sell, dec!(105), dec!(1)).good_till_time(CancelAfter::Hour, false);
+        let order = exchange.place_limit_order(&order).unwrap();
+        let start = exchange.time();
+        exchange.advance_time(start + Duration::minutes(59));
+        assert_eq!(exchange.order(order.id).unwrap().status, OrderStatus::Open);
+        exchange.advance_time(start + Duration::hours(1));
+        assert_eq!(exchange.order(order.id).unwrap().status, OrderStatus::Done);
+        assert_eq!(exchange.best_ask("BTC-USD"), Some(dec!(102)));
+    }
+
+    #[test]
+    fn test_self_trade_prevention() {
+        let mut exchange = exchange();
+        let resting = exchange
+            .place_limit_order(&limit(Side::Sell, dec!(100), dec!(1)))
+            .unwrap();
+
+        let order = limit(Side::Buy, dec!(100), dec!(1))
+            .self_trade_prevention(SelfTradePrevention::CancelNewest);
+        let newest = exchange.place_limit_order(&order).unwrap();
+        assert_eq!(newest.status, OrderStatus::Done);
+        assert_eq!(exchange.order(resting.id).unwrap().status, OrderStatus::Open);
+
+        // decrease and cancel: the smaller order is canceled and the larger one decreased
+        let order = limit(Side::Buy, dec!(101), dec!(1.5));
+        let taker = exchange.place_limit_order(&order).unwrap();
+        let events = exchange.take_events();
+        assert_eq!(fills(&events), vec![(dec!(101), dec!(0.5), Liquidity::Taker)]);
+        assert_eq!(exchange.order(resting.id).unwrap().status, OrderStatus::Done);
+        assert_eq!(taker.filled_size, dec!(0.5));
+        assert_eq!(taker.status, OrderStatus::Done);
+
+        let resting = exchange
+            .place_limit_order(&limit(Side::Sell, dec!(100), dec!(1)))
+            .unwrap();
+        let order = limit(Side::Buy, dec!(100), dec!(1))
+            .self_trade_prevention(SelfTradePrevention::CancelOldest);
+        let newest = exchange.place_limit_order(&order).unwrap();
+        assert_eq!(exchange.order(resting.id).unwrap().status, OrderStatus::Done);
+        assert_eq!(newest.status, OrderStatus::Open);
+        assert_eq!(exchange.best_bid("BTC-USD"), Some(dec!(100)));
+    }
+
+    #[test]
+    fn test_market_order() {
+        let mut exchange = exchange();
+        let order = MarketOrder::with_funds(String::from("BTC-USD"), Side::Buy, dec!(152));
+        let order = exchange.place_market_order(&order).unwrap();
+        let events = exchange.take_events();
+
+        assert_eq!(
+            fills(&events),
+            vec![
+                (dec!(101), dec!(1), Liquidity::Taker),
+                (dec!(102), dec!(0.5), Liquidity::Taker),
+            ]
+        );
+        assert_eq!(dones(&events), vec![(DoneReason::Filled, None)]);
+        assert_eq!(order.executed_value, dec!(152));
+        assert_eq!(exchange.best_ask("BTC-USD"), Some(dec!(102)));
+    }
+
+    #[test]
+    fn test_market_order_fee_in_funds() {
+        let mut exchange = exchange();
+        exchange.set_fee_rates(dec!(0), dec!(0.0025));
+        let order = MarketOrder::with_funds(String::from("BTC-USD"), Side::Buy, dec!(10));
+        let order = exchange.place_market_order(&order).unwrap();
+
+        assert_eq!(order.specified_funds, Some(dec!(10)));
+        assert_eq!(order.funds, Some(dec!(9.97506234)));
+        assert_eq!(
+            fills(&exchange.take_events()),
+            vec![(dec!(101), dec!(0.09876299), Liquidity::Taker)]
+        );
+        assert!(order.executed_value + order.fill_fees <= dec!(10));
+
+        let order = StopOrder::with_funds(String::from("BTC-USD"), Side::Buy, dec!(105), dec!(10));
+        let order = exchange.place_stop_order(&order).unwrap();
+        assert_eq!(order.funds, Some(dec!(9.97506234)));
+    }
+
+    #[test]
+    fn test_stop_order() {
+        let mut exchange = exchange();
+        let order = StopOrder::with_size(String::from("BTC-USD"), Side::Sell, dec!(98.5), dec!(2));
+        let order = exchange.place_stop_order(&order).unwrap();
+        assert_eq!(order.status, OrderStatus::Active);
+
+        exchange.process_trade("BTC-USD", &trade(Side::Buy, dec!(99), dec!(0.5)));
+        assert_eq!(exchange.order(order.id).unwrap().status, OrderStatus::Active);
+
+        exchange.process_trade("BTC-USD", &trade(Side::Buy, dec!(98.5), dec!(0.1)));
+        let events = exchange.take_events();
+        assert_eq!(
+            fills(&events),
+            vec![
+                (dec!(99), dec!(0.5), Liquidity::Taker),
+                (dec!(98), dec!(1.5), Liquidity::Taker),
+            ]
+        );
+        assert_eq!(exchange.order(order.id).unwrap().status, OrderStatus::Done);
+    }
+
+    #[test]
+    fn test_cancel_order() {
+        let mut exchange = exchange();
+        let order = exchange
+            .place_limit_order(&limit(Side::Buy, dec!(95), dec!(1)))
+            .unwrap();
+
+        exchange.cancel_order(order.id).unwrap();
+        assert_eq!(
+            dones(&exchange.take_events()),
+            vec![(DoneReason::Canceled, Some(dec!(1)))]
+        );
+        assert_eq!(exchange.best_bid("BTC-USD"), Some(dec!(99)));
+        assert_eq!(
+            error_message(exchange.cancel_order(order.id).unwrap_err()),
+            "NotFound"
+        );
+    }
+}
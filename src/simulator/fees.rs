@@ -0,0 +1,56 @@
+use rust_decimal::Decimal;
+
+use fills::Liquidity;
+
+/// The fee rates charged on the executed value of the fills, a rate of `0.0025` is a fee of
+/// 0.25%.
+///
+/// The default rates are those of the lowest volume tier of GDAX: no fee for the makers and
+/// 0.25% for the takers.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct FeeRates {
+    pub maker: Decimal,
+    pub taker: Decimal,
+}
+
+impl Default for FeeRates {
+    fn default() -> FeeRates {
+        FeeRates::new(Decimal::ZERO, Decimal::new(25, 4))
+    }
+}
+
+impl FeeRates {
+    pub fn new(maker: Decimal, taker: Decimal) -> FeeRates {
+        FeeRates { maker, taker }
+    }
+
+    /// The rate of a fill providing or taking the liquidity
+    pub fn rate(&self, liquidity: Liquidity) -> Decimal {
+        match liquidity {
+            Liquidity::Maker => self.maker,
+            Liquidity::Taker => self.taker,
+        }
+    }
+
+    /// The fee of a fill of `size` at `price`
+    pub fn fee(&self, price: Decimal, size: Decimal, liquidity: Liquidity) -> Decimal {
+        price * size * self.rate(liquidity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fills::Liquidity;
+    use super::FeeRates;
+
+    #[test]
+    fn test_fee() {
+        let fees = FeeRates::default();
+        assert_eq!(fees.fee(dec!(100), dec!(2), Liquidity::Maker), dec!(0));
+        assert_eq!(fees.fee(dec!(100), dec!(2), Liquidity::Taker), dec!(0.5));
+        assert_eq!(
+            FeeRates::new(dec!(0.001), dec!(0.003)).rate(Liquidity::Maker),
+            dec!(0.001)
+        );
+    }
+}
@@ -0,0 +1,14 @@
+//! This module contains a matching engine simulating GDAX, it is used to paper trade against
+//! recorded market data: the order books of `GetProductOrderBook` or of the `level2` channel and
+//! the trades of `GetTrades`.
+//!
+//! The orders follow the GDAX rules: the price and size limits of the `Product`, the post only
+//! orders, the time in force policies and the self-trade prevention. The simulated exchange
+//! produces the fills and done messages that GDAX would send for the orders.
+
+mod book;
+mod exchange;
+mod fees;
+
+pub use self::exchange::{Event, SimulatedExchange};
+pub use self::fees::FeeRates;
@@ -0,0 +1,684 @@
+//! This module contains a local http server emulating the public end points of GDAX, it is used
+//! to test a `RESTClient` without network access. It requires the `testing` feature, it is
+//! always compiled for the tests of the crate.
+//!
+//! The server answers with the fixtures given to the `Emulator`, in the GDAX format: the order
+//! book levels, the trades pagination (`before`, `after`, `limit` and the `CB-BEFORE`/`CB-AFTER`
+//! headers) and the candles ranges are handled like GDAX does. Failures and latency can be
+//! injected to test the error handling of a client.
+//!
+//! ```ignore
+//! let emulator = Emulator::start().unwrap();
+//! emulator.set_products(products);
+//! emulator.fail_next(Failure::RateLimited);
+//!
+//! let mut client = RESTClient::new(emulator.url(), &handle).unwrap();
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use futures::{future, Future, Stream};
+use futures::sync::oneshot;
+use hyper::{self, StatusCode};
+use hyper::header::ContentType;
+use hyper::server::{Http, Request, Response, Service};
+use percent_encoding::percent_decode;
+use serde::Serialize;
+use serde_json;
+use tokio_core::reactor::{Core, Handle, Timeout};
+
+use currencies::Currency;
+use error::RestError;
+use products::{Candle, Granularity, OrderBook, PriceLevel, Product, Stats, Ticker, Trade,
+               MAX_CANDLES};
+use time::Time;
+
+/// The maximum number of trades in a page
+const MAX_LIMIT: usize = 100;
+const GRANULARITIES: [Granularity; 6] = [
+    Granularity::OneMinute,
+    Granularity::FiveMinutes,
+    Granularity::FifteenMinutes,
+    Granularity::OneHour,
+    Granularity::SixHours,
+    Granularity::OneDay,
+];
+
+/// A failure returned by the emulator instead of the answer of a request
+#[derive(PartialEq, Debug, Clone)]
+pub enum Failure {
+    /// `429 Too Many Requests` with the GDAX message
+    RateLimited,
+    /// `500 Internal Server Error`
+    ServerError,
+    /// A `200 OK` response whose body is not valid json
+    MalformedJson,
+    /// Any status with a GDAX error message
+    Status(StatusCode, String),
+}
+
+/// A GDAX emulator running in its own thread, the server is stopped when it is dropped.
+///
+/// The fixtures, the failures and the latency can be changed at any time, they apply to the
+/// next requests.
+pub struct Emulator {
+    url: String,
+    state: Arc<Mutex<State>>,
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+#[derive(Default)]
+struct State {
+    currencies: Vec<Currency>,
+    products: Vec<Product>,
+    books: HashMap<String, OrderBook<PriceLevel>>,
+    tickers: HashMap<String, Ticker>,
+    trades: HashMap<String, Vec<Trade>>,
+    candles: HashMap<(String, Granularity), Vec<Candle>>,
+    stats: HashMap<String, Stats>,
+    latency: Option<Duration>,
+    failures: VecDeque<Failure>,
+    requests: Vec<String>,
+}
+
+impl Emulator {
+    /// Start a server on a free local port, without fixtures
+    pub fn start() -> Result<Emulator, RestError> {
+        let state = Arc::new(Mutex::new(State::default()));
+        let (addr_sender, addr_receiver) = mpsc::channel();
+        let (shutdown, shutdown_receiver) = oneshot::channel::<()>();
+
+        let service_state = state.clone();
+        let thread = thread::spawn(move || {
+            let server = Core::new().map_err(RestError::from).and_then(|core| {
+                let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+                let handle = core.handle();
+                let serve = Http::new().serve_addr_handle(&addr, &core.handle(), move || {
+                    Ok(EmulatorService {
+                        state: service_state.clone(),
+                        handle: handle.clone(),
+                    })
+                })?;
+                Ok((core, serve))
+            });
+            let (mut core, serve) = match server {
+                Ok((core, serve)) => {
+                    addr_sender
+                        .send(Ok(serve.incoming_ref().local_addr()))
+                        .unwrap();
+                    (core, serve)
+                }
+                Err(error) => {
+                    addr_sender.send(Err(error)).unwrap();
+                    return;
+                }
+            };
+
+            let handle = core.handle();
+            let connections = serve.map_err(|_| ()).for_each(move |connection| {
+                handle.spawn(connection.map(|_| ()).map_err(|_| ()));
+                Ok(())
+            });
+            // the server stops when the emulator is dropped
+            let _ = core.run(connections.select(shutdown_receiver.then(|_| Ok(()))));
+        });
+
+        let addr = addr_receiver
+            .recv()
+            .map_err(|e| RestError::RequestError(e.to_string()))??;
+
+        Ok(Emulator {
+            url: format!("http://{}", addr),
+            state,
+            shutdown: Some(shutdown),
+            thread: Some(thread),
+        })
+    }
+
+    /// The API url to give to `RESTClient::new`
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn set_currencies(&self, currencies: Vec<Currency>) {
+        self.state.lock().unwrap().currencies = currencies;
+    }
+
+    pub fn set_products(&self, products: Vec<Product>) {
+        self.state.lock().unwrap().products = products;
+    }
+
+    /// Set the full book of a product, the levels 1 and 2 return the first levels of each side
+    pub fn set_order_book(&self, product_id: &str, book: OrderBook<PriceLevel>) {
+        self.state
+            .lock()
+            .unwrap()
+            .books
+            .insert(String::from(product_id), book);
+    }
+
+    pub fn set_ticker(&self, product_id: &str, ticker: Ticker) {
+        self.state
+            .lock()
+            .unwrap()
+            .tickers
+            .insert(String::from(product_id), ticker);
+    }
+
+    /// Set the trades of a product, in any order
+    pub fn set_trades(&self, product_id: &str, mut trades: Vec<Trade>) {
+        // GDAX returns the most recent trades first
+        trades.sort_by_key(|trade| ::std::cmp::Reverse(trade.trade_id));
+        self.state
+            .lock()
+            .unwrap()
+            .trades
+            .insert(String::from(product_id), trades);
+    }
+
+    /// Set the candles of a product for a granularity, in any order
+    pub fn set_candles(
+        &self,
+        product_id: &str,
+        granularity: Granularity,
+        mut candles: Vec<Candle>,
+    ) {
+        // GDAX returns the most recent candles first
+        candles.sort_by_key(|candle| ::std::cmp::Reverse(candle.time));
+        self.state
+            .lock()
+            .unwrap()
+            .candles
+            .insert((String::from(product_id), granularity), candles);
+    }
+
+    pub fn set_stats(&self, product_id: &str, stats: Stats) {
+        self.state
+            .lock()
+            .unwrap()
+            .stats
+            .insert(String::from(product_id), stats);
+    }
+
+    /// Delay every response, `None` answers immediately
+    pub fn set_latency(&self, latency: Option<Duration>) {
+        self.state.lock().unwrap().latency = latency;
+    }
+
+    /// Answer the next request with a failure, the failures are queued
+    pub fn fail_next(&self, failure: Failure) {
+        self.state.lock().unwrap().failures.push_back(failure);
+    }
+
+    /// Returns the routes (path and query) of all the requests received by the server
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for Emulator {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+struct EmulatorService {
+    state: Arc<Mutex<State>>,
+    /// The reactor of the server, it delays the responses
+    handle: Handle,
+}
+
+impl Service for EmulatorService {
+    type Request = Request;
+    type Response = Response;
+    type Error = hyper::Error;
+    type Future = Box<Future<Item = Response, Error = hyper::Error>>;
+
+    fn call(&self, request: Request) -> Self::Future {
+        let (response, latency) = {
+            let mut state = self.state.lock().unwrap();
+            state.requests.push(request.uri().to_string());
+            let response = match state.failures.pop_front() {
+                Some(failure) => failure_response(failure),
+                None => state.answer(&request),
+            };
+            (response, state.latency)
+        };
+
+        // the other requests are answered during the delay
+        let timeout = match latency {
+            Some(latency) => Timeout::new(latency, &self.handle),
+            None => return Box::new(future::ok(response)),
+        };
+        match timeout {
+            Ok(timeout) => Box::new(timeout.map(move |_| response).map_err(hyper::Error::from)),
+            Err(error) => Box::new(future::err(hyper::Error::from(error))),
+        }
+    }
+}
+
+impl State {
+    fn answer(&self, request: &Request) -> Response {
+        if *request.method() != hyper::Method::Get {
+            return error_response(StatusCode::NotFound, "NotFound");
+        }
+
+        let query = parse_query(request.query());
+        let segments: Vec<&str> = request
+            .path()
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+
+        match &segments[..] {
+            ["time"] => {
+                let now = Utc::now();
+                json_response(&Time {
+                    iso: now,
+                    epoch: now.timestamp() as f64 + f64::from(now.timestamp_subsec_micros()) / 1e6,
+                })
+            }
+            ["currencies"] => json_response(&self.currencies),
+            ["products"] => json_response(&self.products),
+            ["products", product_id, "book"] => match self.books.get(*product_id) {
+                Some(book) => {
+                    let depth = match query.get("level").map(String::as_str) {
+                        None | Some("1") => 1,
+                        Some("2") => 50,
+                        Some("3") => ::std::usize::MAX,
+                        Some(_) => return error_response(StatusCode::BadRequest, "Invalid level"),
+                    };
+                    json_response(&OrderBook {
+                        sequence: book.sequence,
+                        bids: book.bids.iter().take(depth).cloned().collect(),
+                        asks: book.asks.iter().take(depth).cloned().collect(),
+                    })
+                }
+                None => not_found(),
+            },
+            ["products", product_id, "ticker"] => match self.tickers.get(*product_id) {
+                Some(ticker) => json_response(ticker),
+                None => not_found(),
+            },
+            ["products", product_id, "trades"] => match self.trades.get(*product_id) {
+                Some(trades) => trades_response(trades, &query),
+                None => not_found(),
+            },
+            ["products", product_id, "candles"] => self.candles_response(product_id, &query),
+            ["products", product_id, "stats"] => match self.stats.get(*product_id) {
+                Some(stats) => json_response(stats),
+                None => not_found(),
+            },
+            _ => not_found(),
+        }
+    }
+
+    fn candles_response(&self, product_id: &str, query: &HashMap<String, String>) -> Response {
+        let seconds = query.get("granularity").and_then(|value| value.parse().ok());
+        let granularity = match GRANULARITIES
+            .iter()
+            .find(|granularity| Some(granularity.seconds()) == seconds)
+        {
+            Some(granularity) => *granularity,
+            None => return error_response(StatusCode::BadRequest, "Unsupported granularity"),
+        };
+
+        // both bounds are ignored if one of them is missing
+        let range = match (query.get("start"), query.get("end")) {
+            (Some(start), Some(end)) => match (parse_date(start), parse_date(end)) {
+                (Some(start), Some(end)) => Some((start, end)),
+                _ => return error_response(StatusCode::BadRequest, "Invalid start or end"),
+            },
+            _ => None,
+        };
+        if let Some((start, end)) = range {
+            // both bounds are included in the candles
+            if (end - start).num_seconds() / granularity.seconds() + 1 > MAX_CANDLES {
+                return error_response(
+                    StatusCode::BadRequest,
+                    "granularity too small for the requested time range",
+                );
+            }
+        }
+
+        let candles = self.candles
+            .get(&(String::from(product_id), granularity))
+            .map(|candles| candles.as_slice())
+            .unwrap_or(&[]);
+        let candles: Vec<&Candle> = candles
+            .iter()
+            .filter(|candle| match range {
+                Some((start, end)) => candle.time >= start && candle.time <= end,
+                None => true,
+            })
+            .take(MAX_CANDLES as usize)
+            .collect();
+        json_response(&candles)
+    }
+}
+
+/// Returns a page of trades (the most recent first) and the cursors of the adjacent pages
+fn trades_response(trades: &[Trade], query: &HashMap<String, String>) -> Response {
+    let limit = query
+        .get("limit")
+        .and_then(|limit| limit.parse().ok())
+        .unwrap_or(MAX_LIMIT)
+        .min(MAX_LIMIT);
+
+    let page: Vec<&Trade> = match query.get("before").and_then(|before| before.parse().ok()) {
+        // the page of the trades newer than the cursor, just before it
+        Some(before) => {
+            let newer: Vec<&Trade> = trades
+                .iter()
+                .filter(|trade| trade.trade_id > before)
+                .collect();
+            newer[newer.len().saturating_sub(limit)..].to_vec()
+        }
+        None => {
+            let after = query
+                .get("after")
+                .and_then(|after| after.parse().ok())
+                .unwrap_or(::std::usize::MAX);
+            trades
+                .iter()
+                .filter(|trade| trade.trade_id < after)
+                .take(limit)
+                .collect()
+        }
+    };
+
+    let mut response = json_response(&page);
+    if let (Some(first), Some(last)) = (page.first(), page.last()) {
+        let headers = response.headers_mut();
+        headers.set_raw("CB-BEFORE", first.trade_id.to_string());
+        headers.set_raw("CB-AFTER", last.trade_id.to_string());
+    }
+    response
+}
+
+fn json_response<T: Serialize>(value: &T) -> Response {
+    // the fixtures are always serializable
+    let body = serde_json::to_string(value).unwrap();
+    Response::new()
+        .with_header(ContentType::json())
+        .with_body(body)
+}
+
+#[derive(Serialize)]
+struct ErrorMessage<'a> {
+    message: &'a str,
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    json_response(&ErrorMessage { message }).with_status(status)
+}
+
+fn not_found() -> Response {
+    error_response(StatusCode::NotFound, "NotFound")
+}
+
+fn failure_response(failure: Failure) -> Response {
+    match failure {
+        Failure::RateLimited => error_response(StatusCode::TooManyRequests, "Rate limit exceeded"),
+        Failure::ServerError => {
+            error_response(StatusCode::InternalServerError, "Internal server error")
+        }
+        Failure::MalformedJson => Response::new()
+            .with_header(ContentType::json())
+            .with_body("{\"message\": "),
+        Failure::Status(status, message) => error_response(status, &message),
+    }
+}
+
+fn parse_query(query: Option<&str>) -> HashMap<String, String> {
+    query
+        .unwrap_or("")
+        .split('&')
+        .filter_map(|pair| {
+            let mut pair = pair.splitn(2, '=');
+            match (pair.next(), pair.next()) {
+                (Some(attribute), Some(value)) => Some((
+                    String::from(attribute),
+                    percent_decode(value.as_bytes())
+                        .decode_utf8_lossy()
+                        .into_owned(),
+                )),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Parse a date as sent by `GetHistoricRates` (`2017-12-21 10:00:00`) or in the ISO 8601 format
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    date.parse().ok().or_else(|| {
+        NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S%.f")
+            .ok()
+            .map(|date| DateTime::from_utc(date, Utc))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use chrono::{TimeZone, Utc};
+    use futures::{Future, Stream};
+    use hyper::StatusCode;
+    use serde_json;
+    use tokio_core::reactor::Core;
+
+    use error::RestError;
+    use products::{Candle, Get24hrStats, GetProductOrderBook, GetProducts, GetTrades, Granularity,
+                   Level, OrderBook, PriceLevel, Product, Trade};
+    use time::GetTime;
+    use {Cursor, Pagination, RESTClient, RetryPolicy};
+    use super::{Emulator, Failure};
+
+    fn client(emulator: &Emulator, core: &Core) -> RESTClient {
+        let mut client = RESTClient::new(emulator.url(), &core.handle()).unwrap();
+        client.set_rate_limiter(None);
+        client.set_retry_policy(RetryPolicy::never());
+        client
+    }
+
+    fn trades(count: usize) -> Vec<Trade> {
+        (1..count + 1)
+            .map(|trade_id| {
+                serde_json::from_str(&format!(
+                    r#"{{"time": "2017-12-21T10:00:0{}Z", "trade_id": {}, "price": "10.0",
+                         "size": "0.5", "side": "buy"}}"#,
+                    trade_id % 10,
+                    trade_id
+                )).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_public_end_points() {
+        let emulator = Emulator::start().unwrap();
+        let products = r#"[{
+            "id": "BTC-USD", "base_currency": "BTC", "quote_currency": "USD",
+            "base_min_size": "0.001", "base_max_size": "70", "quote_increment": "0.01",
+            "display_name": "BTC/USD", "status": "online"
+        }]"#;
+        emulator.set_products(serde_json::from_str(products).unwrap());
+        emulator.set_stats(
+            "BTC-USD",
+            serde_json::from_str(
+                r#"{"open": "1", "high": "2", "low": "0.5", "volume": "10", "last": "1.5",
+                    "volume_30day": "300"}"#,
+            ).unwrap(),
+        );
+        let mut core = Core::new().unwrap();
+        let mut client = client(&emulator, &core);
+
+        let expected: Vec<Product> = serde_json::from_str(products).unwrap();
+        assert_eq!(core.run(client.send_request(&GetProducts::new())).unwrap(), expected);
+        let stats = core.run(client.send_request(&Get24hrStats::new(String::from("BTC-USD"))))
+            .unwrap();
+        assert_eq!(stats.last, dec!(1.5));
+        assert!(core.run(client.send_request(&GetTime::new())).is_ok());
+
+        match core.run(client.send_request(&Get24hrStats::new(String::from("ETH-USD")))) {
+            Err(RestError::ApiError(error)) => {
+                assert!(error.is_not_found());
+                assert_eq!(error.message(), "NotFound");
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+        let requests = emulator.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[3], "/products/ETH-USD/stats");
+    }
+
+    #[test]
+    fn test_order_book_levels() {
+        let emulator = Emulator::start().unwrap();
+        let book: OrderBook<PriceLevel> = serde_json::from_str(
+            r#"{"sequence": 3, "bids": [["10", "1", 2], ["9", "1", 1]], "asks": [["11", "2", 3]]}"#,
+        ).unwrap();
+        emulator.set_order_book("BTC-USD", book.clone());
+        let mut core = Core::new().unwrap();
+        let mut client = client(&emulator, &core);
+
+        let best = core.run(client.send_request(&GetProductOrderBook::new(
+            String::from("BTC-USD"),
+            Level::Best,
+        ))).unwrap();
+        assert_eq!(best.bids, vec![book.bids[0].clone()]);
+        assert_eq!(best.asks, book.asks);
+
+        let full = core.run(client.send_request(&GetProductOrderBook::new(
+            String::from("BTC-USD"),
+            Level::Full,
+        ))).unwrap();
+        assert_eq!(full, book);
+    }
+
+    #[test]
+    fn test_paginate_trades() {
+        let emulator = Emulator::start().unwrap();
+        emulator.set_trades("BTC-USD", trades(5));
+        let mut core = Core::new().unwrap();
+        let mut client = client(&emulator, &core);
+
+        let pagination = Pagination {
+            page: Cursor::After(5),
+            limit: Some(2),
+        };
+        let result: Vec<usize> = core.run(
+            client
+                .paginate(&GetTrades::new(String::from("BTC-USD"), Some(pagination)))
+                .map(|trade| trade.trade_id)
+                .collect(),
+        ).unwrap();
+        assert_eq!(result, vec![4, 3, 2, 1]);
+
+        let pagination = Pagination {
+            page: Cursor::Before(1),
+            limit: Some(3),
+        };
+        let page = core.run(client.send_paginated_request(&GetTrades::new(
+            String::from("BTC-USD"),
+            Some(pagination),
+        ))).unwrap();
+        let result: Vec<usize> = page.items.iter().map(|trade| trade.trade_id).collect();
+        assert_eq!(result, vec![4, 3, 2]);
+        assert_eq!((page.before, page.after), (Some(4), Some(2)));
+    }
+
+    #[test]
+    fn test_candles() {
+        let emulator = Emulator::start().unwrap();
+        let start = Utc.ymd(2017, 12, 21).and_hms(0, 0, 0);
+        let candles: Vec<Candle> = (0..1000)
+            .map(|minute| {
+                let time = start.timestamp() + minute * 60;
+                serde_json::from_str(&format!("[{}, 1, 2, 1, 2, 5]", time)).unwrap()
+            })
+            .collect();
+        emulator.set_candles("BTC-USD", Granularity::OneMinute, candles);
+        let mut core = Core::new().unwrap();
+        let mut client = client(&emulator, &core);
+
+        let result = core.run(client.get_candles(
+            "BTC-USD",
+            start,
+            start + ::chrono::Duration::minutes(999),
+            Granularity::OneMinute,
+        )).unwrap();
+        assert_eq!(result.len(), 1000);
+        assert_eq!(result[999].time, start + ::chrono::Duration::minutes(999));
+        assert_eq!(emulator.requests().len(), 4);
+    }
+
+    #[test]
+    fn test_failures() {
+        let emulator = Emulator::start().unwrap();
+        let mut core = Core::new().unwrap();
+        let mut client = client(&emulator, &core);
+
+        emulator.fail_next(Failure::RateLimited);
+        match core.run(client.send_request(&GetProducts::new())) {
+            Err(RestError::ApiError(error)) => assert!(error.is_rate_limited()),
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        emulator.fail_next(Failure::MalformedJson);
+        match core.run(client.send_request(&GetProducts::new())) {
+            Err(RestError::ParseError(_)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        emulator.fail_next(Failure::Status(StatusCode::BadRequest, String::from("Oops")));
+        match core.run(client.send_request(&GetProducts::new())) {
+            Err(RestError::ApiError(error)) => assert_eq!(error.message(), "Oops"),
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        // the failures are transient, the request succeeds when it is retried
+        emulator.fail_next(Failure::ServerError);
+        emulator.fail_next(Failure::RateLimited);
+        client.set_retry_policy(RetryPolicy::new(
+            3,
+            Duration::from_millis(1),
+            Duration::from_millis(1),
+        ));
+        assert!(core.run(client.send_request(&GetProducts::new())).is_ok());
+        assert_eq!(emulator.requests().len(), 6);
+    }
+
+    #[test]
+    fn test_latency() {
+        let emulator = Emulator::start().unwrap();
+        emulator.set_latency(Some(Duration::from_millis(100)));
+        let mut core = Core::new().unwrap();
+        let mut client = client(&emulator, &core);
+
+        let start = Instant::now();
+        assert!(core.run(client.send_request(&GetProducts::new())).is_ok());
+        assert!(start.elapsed() >= Duration::from_millis(100));
+
+        // the delays of concurrent requests overlap
+        emulator.set_latency(Some(Duration::from_millis(500)));
+        let start = Instant::now();
+        let requests = client
+            .send_request(&GetProducts::new())
+            .join(client.send_request(&GetTime::new()));
+        assert!(core.run(requests).is_ok());
+        assert!(start.elapsed() < Duration::from_millis(1000));
+    }
+}
@@ -70,4 +70,12 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let json = r#"{"iso":"2015-01-07T23:47:25.201Z","epoch":1420674445.201}"#;
+        let result: Time = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_string(&result).unwrap(), json);
+    }
 }
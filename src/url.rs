@@ -3,13 +3,13 @@ use std::fmt;
 use itertools::join;
 use percent_encoding::{percent_encode, DEFAULT_ENCODE_SET};
 
-#[derive(PartialEq, Debug, Default)]
+#[derive(Clone, PartialEq, Debug, Default)]
 pub struct Route {
     path: Vec<String>,
     query: Vec<AttributeValue>,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 struct AttributeValue {
     attribute: String,
     value: String,
@@ -0,0 +1,357 @@
+use futures::{future, Async, AsyncSink, Future, Poll, Sink, StartSend, Stream};
+use serde_json;
+use tokio_core::reactor::Handle;
+use ws::{ClientBuilder, OwnedMessage};
+use ws::async::Client;
+use ws::async::Stream as NetworkStream;
+
+use auth::Credentials;
+use error::{ParseError, RestError};
+use super::{Channel, Message, OrderUpdate, Request};
+
+pub const WEBSOCKET_FEED: &str = "wss://ws-feed.gdax.com";
+pub const SANDBOX_WEBSOCKET_FEED: &str = "wss://ws-feed-public.sandbox.gdax.com";
+
+/// The client of the GDAX websocket feed, it opens a new connection for each call to `connect`
+/// or `subscribe`.
+pub struct WebSocketClient {
+    feed_url: String,
+    handle: Handle,
+    credentials: Option<Credentials>,
+}
+
+impl WebSocketClient {
+    /// Create a new `WebSocketClient` object with a specified feed URL, for most cases, you
+    /// should use `WebSocketClient::default` or `WebSocketClient::sandbox` to connect to GDAX
+    pub fn new(feed_url: &str, handle: &Handle) -> WebSocketClient {
+        WebSocketClient {
+            feed_url: String::from(feed_url),
+            handle: handle.clone(),
+            credentials: None,
+        }
+    }
+
+    /// Create a new `WebSocketClient` object that signs its subscriptions with `credentials`,
+    /// it is required to subscribe to the `user` channel. Use `WEBSOCKET_FEED` or
+    /// `SANDBOX_WEBSOCKET_FEED` as feed URL to connect to GDAX.
+    pub fn with_credentials(
+        feed_url: &str,
+        credentials: Credentials,
+        handle: &Handle,
+    ) -> WebSocketClient {
+        let mut client = WebSocketClient::new(feed_url, handle);
+        client.credentials = Some(credentials);
+        client
+    }
+
+    /// Create a new `WebSocketClient` connected to the GDAX production feed
+    pub fn default(handle: &Handle) -> WebSocketClient {
+        WebSocketClient::new(WEBSOCKET_FEED, handle)
+    }
+
+    /// Create a new `WebSocketClient` connected to the GDAX sandbox feed
+    pub fn sandbox(handle: &Handle) -> WebSocketClient {
+        WebSocketClient::new(SANDBOX_WEBSOCKET_FEED, handle)
+    }
+
+    /// Open a connection without any subscription, GDAX closes it if nothing is subscribed
+    /// within 5 seconds.
+    pub fn connect(&self) -> Box<Future<Item = Feed, Error = RestError>> {
+        let builder = match ClientBuilder::new(&self.feed_url) {
+            Ok(builder) => builder,
+            Err(e) => return Box::new(future::err(RestError::UriError(e.to_string()))),
+        };
+
+        let credentials = self.credentials.clone();
+        let work = builder
+            .async_connect(None, &self.handle)
+            .map(|(client, _)| Feed {
+                client,
+                credentials,
+                pending: None,
+            })
+            .map_err(RestError::from);
+
+        Box::new(work)
+    }
+
+    /// Open a connection and subscribe to `channels` for all the `product_ids`.
+    pub fn subscribe(
+        &self,
+        product_ids: Vec<String>,
+        channels: Vec<Channel>,
+    ) -> Box<Future<Item = Feed, Error = RestError>> {
+        let work = self.connect()
+            .and_then(|feed| feed.send(Request::subscribe(product_ids, channels)));
+
+        Box::new(work)
+    }
+}
+
+/// An open connection to the websocket feed, it is a `Stream` of the received messages and a
+/// `Sink` of requests to change the subscriptions.
+///
+/// The stream ends when GDAX closes the connection, it fails if a message of a known type
+/// cannot be parsed. If the feed has been opened by a client with credentials, the
+/// subscriptions are signed when they are sent.
+pub struct Feed {
+    client: Client<Box<NetworkStream + Send>>,
+    credentials: Option<Credentials>,
+    /// A control message that must be sent before reading the next messages
+    pending: Option<OwnedMessage>,
+}
+
+impl Feed {
+    /// Keep only the events of the orders (from the `user` or `full` channels), the stream
+    /// fails if GDAX sends an error message.
+    pub fn order_updates(self) -> Box<Stream<Item = OrderUpdate, Error = RestError>> {
+        let updates = self.filter_map(|message| match message {
+            Message::Error(error) => Some(Err(RestError::WebSocketError(error.message))),
+            message => OrderUpdate::from_message(message).map(Ok),
+        }).and_then(|update| update);
+
+        Box::new(updates)
+    }
+
+    fn flush_pending(&mut self) -> Result<(), RestError> {
+        if let Some(message) = self.pending.take() {
+            if let AsyncSink::NotReady(message) = self.client.start_send(message)? {
+                self.pending = Some(message);
+            }
+        }
+        self.client.poll_complete()?;
+        Ok(())
+    }
+}
+
+impl Stream for Feed {
+    type Item = Message;
+    type Error = RestError;
+
+    fn poll(&mut self) -> Poll<Option<Message>, RestError> {
+        loop {
+            self.flush_pending()?;
+            let message = match self.client.poll()? {
+                Async::Ready(message) => message,
+                Async::NotReady => return Ok(Async::NotReady),
+            };
+
+            match message {
+                Some(OwnedMessage::Text(text)) => {
+                    return parse_message(text).map(|message| Async::Ready(Some(message)))
+                }
+                Some(OwnedMessage::Ping(data)) => self.pending = Some(OwnedMessage::Pong(data)),
+                Some(OwnedMessage::Close(_)) | None => return Ok(Async::Ready(None)),
+                Some(OwnedMessage::Binary(_)) | Some(OwnedMessage::Pong(_)) => {}
+            }
+        }
+    }
+}
+
+impl Sink for Feed {
+    type SinkItem = Request;
+    type SinkError = RestError;
+
+    fn start_send(&mut self, request: Request) -> StartSend<Request, RestError> {
+        let signed_request = match self.credentials {
+            Some(ref credentials) if request.is_unsigned_subscription() => {
+                request.clone().authenticate(credentials)
+            }
+            _ => request.clone(),
+        };
+        // the serialization of the requests cannot fail
+        let text = serde_json::to_string(&signed_request).unwrap();
+        match self.client.start_send(OwnedMessage::Text(text))? {
+            AsyncSink::Ready => Ok(AsyncSink::Ready),
+            AsyncSink::NotReady(_) => Ok(AsyncSink::NotReady(request)),
+        }
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), RestError> {
+        self.client.poll_complete().map_err(RestError::from)
+    }
+}
+
+fn parse_message(text: String) -> Result<Message, RestError> {
+    serde_json::from_str(&text)
+        .map_err(|e| RestError::ParseError(ParseError::new(text.clone(), e.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{stream, Future, Sink, Stream};
+    use tokio_core::net::TcpListener;
+    use tokio_core::reactor::Core;
+    use ws::{OwnedMessage, WebSocketError};
+    use ws::async::server::IntoWs;
+
+    use auth::Credentials;
+    use error::RestError;
+    use super::{Channel, Message, OrderUpdate, WebSocketClient};
+
+    /// Start a local websocket server that checks the first message sent by the client and then
+    /// answers with `messages`
+    fn serve<F>(core: &Core, check_request: F, messages: Vec<OwnedMessage>) -> String
+    where
+        F: FnOnce(String) + 'static,
+    {
+        let handle = core.handle();
+        let listener = TcpListener::bind(&"127.0.0.1:0".parse().unwrap(), &handle).unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        let server = listener
+            .incoming()
+            .into_future()
+            .map_err(|_| ())
+            .and_then(|(connection, _)| {
+                let (tcp_stream, _) = connection.unwrap();
+                tcp_stream.into_ws().map_err(|_| ())
+            })
+            .and_then(|upgrade| upgrade.accept().map_err(|_| ()))
+            .and_then(|(client, _)| client.into_future().map_err(|_| ()))
+            .and_then(move |(request, client)| {
+                match request {
+                    Some(OwnedMessage::Text(text)) => check_request(text),
+                    request => panic!("unexpected request {:?}", request),
+                }
+                client
+                    .send_all(stream::iter_ok::<_, WebSocketError>(messages))
+                    .map_err(|_| ())
+            })
+            .map(|_| ());
+        handle.spawn(server);
+
+        url
+    }
+
+    #[test]
+    fn test_subscribe() {
+        let mut core = Core::new().unwrap();
+        let url = serve(
+            &core,
+            |request| {
+                assert_eq!(
+                    request,
+                    "{\"type\":\"subscribe\",\"product_ids\":[\"BTC-USD\"],\
+                     \"channels\":[\"heartbeat\"]}"
+                )
+            },
+            vec![
+                OwnedMessage::Text(String::from(
+                    "{\"type\":\"subscriptions\",\"channels\":[{\"name\":\"heartbeat\",\
+                     \"product_ids\":[\"BTC-USD\"]}]}",
+                )),
+                OwnedMessage::Ping(vec![1, 2, 3]),
+                OwnedMessage::Text(String::from("{\"type\":\"auction\"}")),
+                OwnedMessage::Text(String::from(
+                    "{\"type\":\"heartbeat\",\"sequence\":90,\"last_trade_id\":20,\
+                     \"product_id\":\"BTC-USD\",\"time\":\"2014-11-07T08:19:28.464459Z\"}",
+                )),
+                OwnedMessage::Close(None),
+            ],
+        );
+
+        let client = WebSocketClient::new(&url, &core.handle());
+        let work = client
+            .subscribe(vec![String::from("BTC-USD")], vec![Channel::Heartbeat])
+            .and_then(|feed| feed.collect());
+        let messages = core.run(work).unwrap();
+
+        assert_eq!(messages.len(), 3);
+        match messages[0] {
+            Message::Subscriptions(ref subscriptions) => {
+                assert_eq!(subscriptions.channels[0].name, Channel::Heartbeat)
+            }
+            ref message => panic!("unexpected message {:?}", message),
+        }
+        match messages[1] {
+            Message::Unknown(ref value) => assert_eq!(value["type"], "auction"),
+            ref message => panic!("unexpected message {:?}", message),
+        }
+        match messages[2] {
+            Message::Heartbeat(ref heartbeat) => assert_eq!(heartbeat.sequence, 90),
+            ref message => panic!("unexpected message {:?}", message),
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let mut core = Core::new().unwrap();
+        let url = serve(
+            &core,
+            |_| {},
+            vec![OwnedMessage::Text(String::from("not json"))],
+        );
+
+        let client = WebSocketClient::new(&url, &core.handle());
+        let work = client
+            .subscribe(vec![String::from("BTC-USD")], vec![Channel::Ticker])
+            .and_then(|feed| feed.collect());
+
+        match core.run(work) {
+            Err(RestError::ParseError(error)) => assert_eq!(error.content(), "not json"),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_connection_error() {
+        let mut core = Core::new().unwrap();
+        let client = WebSocketClient::new("not a url", &core.handle());
+
+        match core.run(client.connect()) {
+            Err(RestError::UriError(_)) => {}
+            Err(error) => panic!("unexpected error {:?}", error),
+            Ok(_) => panic!("the connection should fail"),
+        }
+    }
+
+    #[test]
+    fn test_order_updates() {
+        let mut core = Core::new().unwrap();
+        let url = serve(
+            &core,
+            |request| {
+                assert!(request.starts_with(
+                    "{\"type\":\"subscribe\",\"product_ids\":[\"BTC-USD\"],\
+                     \"channels\":[\"user\"],\"signature\":"
+                ));
+                assert!(request.contains("\"key\":\"key\",\"passphrase\":\"pass\""));
+            },
+            vec![
+                OwnedMessage::Text(String::from(
+                    "{\"type\":\"subscriptions\",\"channels\":[{\"name\":\"user\",\
+                     \"product_ids\":[\"BTC-USD\"]}]}",
+                )),
+                OwnedMessage::Text(String::from(
+                    "{\"type\":\"open\",\"time\":\"2014-11-07T08:19:27.028459Z\",\
+                     \"product_id\":\"BTC-USD\",\"sequence\":10,\
+                     \"order_id\":\"d50ec984-77a8-460a-b958-66f114b0de9b\",\"price\":\"200.2\",\
+                     \"remaining_size\":\"1.00\",\"side\":\"sell\"}",
+                )),
+                OwnedMessage::Text(String::from(
+                    "{\"type\":\"error\",\"message\":\"Authentication Failed\"}",
+                )),
+            ],
+        );
+
+        let credentials = Credentials::new("key", "Z2RheF9ycyBzZWNyZXQga2V5", "pass").unwrap();
+        let client = WebSocketClient::with_credentials(&url, credentials, &core.handle());
+        let updates = client
+            .subscribe(vec![String::from("BTC-USD")], vec![Channel::User])
+            .map(|feed| feed.order_updates())
+            .flatten_stream();
+        let (update, updates) = core.run(updates.into_future()).map_err(|(e, _)| e).unwrap();
+
+        match update {
+            Some(OrderUpdate::Open(open)) => assert_eq!(open.remaining_size, dec!(1.0)),
+            update => panic!("unexpected update {:?}", update),
+        }
+        match core.run(updates.into_future()) {
+            Err((RestError::WebSocketError(message), _)) => {
+                assert_eq!(message, "Authentication Failed")
+            }
+            _ => panic!("the error message should fail the stream"),
+        }
+    }
+}
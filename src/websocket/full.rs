@@ -0,0 +1,368 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use orders::OrderType;
+use products::{Side, Trade};
+use serde_util::{deserialize_from_str, deserialize_option_from_str};
+
+/// A valid order has been received by the matching engine, it is not yet on the book. The limit
+/// orders have a size and a price, the market orders have a size or funds.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct Received {
+    pub time: DateTime<Utc>,
+    pub product_id: String,
+    pub sequence: usize,
+    pub order_id: Uuid,
+    /// Only sent on the `user` channel for the orders placed with a `client_oid`
+    pub client_oid: Option<Uuid>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")] pub size: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")] pub price: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")] pub funds: Option<Decimal>,
+    pub side: Side,
+    pub order_type: OrderType,
+    /// The user fields are only sent on the authenticated channels for our own orders
+    pub user_id: Option<String>,
+    pub profile_id: Option<Uuid>,
+}
+
+/// The remaining part of a limit order is now on the book.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct Open {
+    pub time: DateTime<Utc>,
+    pub product_id: String,
+    pub sequence: usize,
+    pub order_id: Uuid,
+    #[serde(deserialize_with = "deserialize_from_str")] pub price: Decimal,
+    #[serde(deserialize_with = "deserialize_from_str")] pub remaining_size: Decimal,
+    pub side: Side,
+    pub user_id: Option<String>,
+    pub profile_id: Option<Uuid>,
+}
+
+#[derive(Deserialize, PartialEq, Debug, Copy, Clone)]
+pub enum DoneReason {
+    #[serde(rename = "filled")] Filled,
+    #[serde(rename = "canceled")] Canceled,
+}
+
+/// The order is no longer on the book, this message is sent for all the received orders even
+/// if they have never been open. The market orders have no price and no remaining size.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct Done {
+    pub time: DateTime<Utc>,
+    pub product_id: String,
+    pub sequence: usize,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")] pub price: Option<Decimal>,
+    pub order_id: Uuid,
+    pub reason: DoneReason,
+    pub side: Side,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub remaining_size: Option<Decimal>,
+    pub user_id: Option<String>,
+    pub profile_id: Option<Uuid>,
+}
+
+/// A trade between a maker and a taker order, the side is the side of the maker order.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct Match {
+    pub trade_id: usize,
+    pub sequence: usize,
+    pub maker_order_id: Uuid,
+    pub taker_order_id: Uuid,
+    pub time: DateTime<Utc>,
+    pub product_id: String,
+    #[serde(deserialize_with = "deserialize_from_str")] pub size: Decimal,
+    #[serde(deserialize_with = "deserialize_from_str")] pub price: Decimal,
+    pub side: Side,
+    /// The user fields are only sent on the authenticated channels, `user_id` and `profile_id`
+    /// are the ones of our order (maker or taker).
+    pub user_id: Option<String>,
+    pub profile_id: Option<Uuid>,
+    pub maker_user_id: Option<String>,
+    pub maker_profile_id: Option<Uuid>,
+    pub taker_user_id: Option<String>,
+    pub taker_profile_id: Option<Uuid>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub maker_fee_rate: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub taker_fee_rate: Option<Decimal>,
+}
+
+impl Match {
+    /// The trade as it is returned by the `GetTrades` end point
+    pub fn to_trade(&self) -> Trade {
+        Trade {
+            time: self.time,
+            trade_id: self.trade_id,
+            price: self.price,
+            size: self.size,
+            side: self.side,
+        }
+    }
+}
+
+/// An order has been modified by a self-trade prevention, the limit orders have a new size and
+/// the market orders have new funds.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct Change {
+    pub time: DateTime<Utc>,
+    pub sequence: usize,
+    pub order_id: Uuid,
+    pub product_id: String,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub new_size: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub old_size: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub new_funds: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub old_funds: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")] pub price: Option<Decimal>,
+    pub side: Side,
+    pub user_id: Option<String>,
+    pub profile_id: Option<Uuid>,
+}
+
+/// A buy stop order is an `Entry`, a sell stop order is a `Loss`.
+#[derive(Deserialize, PartialEq, Debug, Copy, Clone)]
+pub enum StopType {
+    #[serde(rename = "entry")] Entry,
+    #[serde(rename = "loss")] Loss,
+}
+
+/// A stop order has been triggered, this message is only sent to its owner.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct Activate {
+    pub product_id: String,
+    /// The time in seconds since the epoch
+    #[serde(deserialize_with = "deserialize_from_str")] pub timestamp: Decimal,
+    pub user_id: String,
+    pub profile_id: Uuid,
+    pub order_id: Uuid,
+    pub stop_type: StopType,
+    pub side: Side,
+    #[serde(deserialize_with = "deserialize_from_str")] pub stop_price: Decimal,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")] pub size: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")] pub funds: Option<Decimal>,
+    #[serde(deserialize_with = "deserialize_from_str")] pub taker_fee_rate: Decimal,
+    pub private: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use serde_json;
+    use uuid::Uuid;
+
+    use orders::OrderType;
+    use products::{Side, Trade};
+    use super::{Activate, Change, Done, DoneReason, Match, Open, Received, StopType};
+
+    #[test]
+    fn test_deserialize_received() {
+        let json = "{\"time\":\"2014-11-07T08:19:27.028459Z\",\"product_id\":\"BTC-USD\",\
+                    \"sequence\":10,\"order_id\":\"d50ec984-77a8-460a-b958-66f114b0de9b\",\
+                    \"size\":\"1.34\",\"price\":\"502.1\",\"side\":\"buy\",\
+                    \"order_type\":\"limit\"}";
+
+        let result: Received = serde_json::from_str(json).unwrap();
+        let expected = Received {
+            time: Utc.ymd(2014, 11, 7).and_hms_micro(8, 19, 27, 28_459),
+            product_id: String::from("BTC-USD"),
+            sequence: 10,
+            order_id: Uuid::parse_str("d50ec984-77a8-460a-b958-66f114b0de9b").unwrap(),
+            client_oid: None,
+            size: Some(dec!(1.34)),
+            price: Some(dec!(502.1)),
+            funds: None,
+            side: Side::Buy,
+            order_type: OrderType::Limit,
+            user_id: None,
+            profile_id: None,
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deserialize_received_market() {
+        let json = "{\"time\":\"2014-11-09T08:19:27.028459Z\",\"product_id\":\"BTC-USD\",\
+                    \"sequence\":12,\"order_id\":\"dddec984-77a8-460a-b958-66f114b0de9b\",\
+                    \"funds\":\"3000.234\",\"side\":\"buy\",\"order_type\":\"market\"}";
+
+        let result: Received = serde_json::from_str(json).unwrap();
+
+        assert_eq!(result.size, None);
+        assert_eq!(result.price, None);
+        assert_eq!(result.funds, Some(dec!(3000.234)));
+        assert_eq!(result.order_type, OrderType::Market);
+    }
+
+    #[test]
+    fn test_deserialize_open() {
+        let json = "{\"time\":\"2014-11-07T08:19:27.028459Z\",\"product_id\":\"BTC-USD\",\
+                    \"sequence\":10,\"order_id\":\"d50ec984-77a8-460a-b958-66f114b0de9b\",\
+                    \"price\":\"200.2\",\"remaining_size\":\"1.00\",\"side\":\"sell\"}";
+
+        let result: Open = serde_json::from_str(json).unwrap();
+        let expected = Open {
+            time: Utc.ymd(2014, 11, 7).and_hms_micro(8, 19, 27, 28_459),
+            product_id: String::from("BTC-USD"),
+            sequence: 10,
+            order_id: Uuid::parse_str("d50ec984-77a8-460a-b958-66f114b0de9b").unwrap(),
+            price: dec!(200.2),
+            remaining_size: dec!(1.0),
+            side: Side::Sell,
+            user_id: None,
+            profile_id: None,
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deserialize_done() {
+        let json = "{\"time\":\"2014-11-07T08:19:27.028459Z\",\"product_id\":\"BTC-USD\",\
+                    \"sequence\":10,\"price\":\"200.2\",\
+                    \"order_id\":\"d50ec984-77a8-460a-b958-66f114b0de9b\",\"reason\":\"filled\",\
+                    \"side\":\"sell\",\"remaining_size\":\"0\"}";
+
+        let result: Done = serde_json::from_str(json).unwrap();
+        let expected = Done {
+            time: Utc.ymd(2014, 11, 7).and_hms_micro(8, 19, 27, 28_459),
+            product_id: String::from("BTC-USD"),
+            sequence: 10,
+            price: Some(dec!(200.2)),
+            order_id: Uuid::parse_str("d50ec984-77a8-460a-b958-66f114b0de9b").unwrap(),
+            reason: DoneReason::Filled,
+            side: Side::Sell,
+            remaining_size: Some(dec!(0.0)),
+            user_id: None,
+            profile_id: None,
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deserialize_match() {
+        let json = "{\"trade_id\":10,\"sequence\":50,\
+                    \"maker_order_id\":\"ac928c66-ca53-498f-9c13-a110027a60e8\",\
+                    \"taker_order_id\":\"132fb6ae-456b-4654-b4e0-d681ac05cea1\",\
+                    \"time\":\"2014-11-07T08:19:27.028459Z\",\"product_id\":\"BTC-USD\",\
+                    \"size\":\"5.23512\",\"price\":\"400.23\",\"side\":\"sell\"}";
+
+        let result: Match = serde_json::from_str(json).unwrap();
+        let time = Utc.ymd(2014, 11, 7).and_hms_micro(8, 19, 27, 28_459);
+        let expected = Match {
+            trade_id: 10,
+            sequence: 50,
+            maker_order_id: Uuid::parse_str("ac928c66-ca53-498f-9c13-a110027a60e8").unwrap(),
+            taker_order_id: Uuid::parse_str("132fb6ae-456b-4654-b4e0-d681ac05cea1").unwrap(),
+            time,
+            product_id: String::from("BTC-USD"),
+            size: dec!(5.23512),
+            price: dec!(400.23),
+            side: Side::Sell,
+            user_id: None,
+            profile_id: None,
+            maker_user_id: None,
+            maker_profile_id: None,
+            taker_user_id: None,
+            taker_profile_id: None,
+            maker_fee_rate: None,
+            taker_fee_rate: None,
+        };
+
+        assert_eq!(result, expected);
+        assert_eq!(
+            result.to_trade(),
+            Trade {
+                time,
+                trade_id: 10,
+                price: dec!(400.23),
+                size: dec!(5.23512),
+                side: Side::Sell,
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_change() {
+        let json = "{\"time\":\"2014-11-07T08:19:27.028459Z\",\"sequence\":80,\
+                    \"order_id\":\"ac928c66-ca53-498f-9c13-a110027a60e8\",\
+                    \"product_id\":\"BTC-USD\",\"new_size\":\"5.23512\",\
+                    \"old_size\":\"12.234412\",\"price\":\"400.23\",\"side\":\"sell\"}";
+
+        let result: Change = serde_json::from_str(json).unwrap();
+        let expected = Change {
+            time: Utc.ymd(2014, 11, 7).and_hms_micro(8, 19, 27, 28_459),
+            sequence: 80,
+            order_id: Uuid::parse_str("ac928c66-ca53-498f-9c13-a110027a60e8").unwrap(),
+            product_id: String::from("BTC-USD"),
+            new_size: Some(dec!(5.23512)),
+            old_size: Some(dec!(12.234412)),
+            new_funds: None,
+            old_funds: None,
+            price: Some(dec!(400.23)),
+            side: Side::Sell,
+            user_id: None,
+            profile_id: None,
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deserialize_activate() {
+        let json = "{\"product_id\":\"test-product\",\"timestamp\":\"1483736448.299000\",\
+                    \"user_id\":\"12\",\"profile_id\":\"30000727-d308-cf50-7b1c-c06deb1934fc\",\
+                    \"order_id\":\"7b52009b-64fd-0a2a-49e6-d8a939753077\",\
+                    \"stop_type\":\"entry\",\"side\":\"buy\",\"stop_price\":\"80\",\
+                    \"size\":\"2\",\"funds\":\"50\",\"taker_fee_rate\":\"0.0025\",\
+                    \"private\":true}";
+
+        let result: Activate = serde_json::from_str(json).unwrap();
+        let expected = Activate {
+            product_id: String::from("test-product"),
+            timestamp: dec!(1483736448.299),
+            user_id: String::from("12"),
+            profile_id: Uuid::parse_str("30000727-d308-cf50-7b1c-c06deb1934fc").unwrap(),
+            order_id: Uuid::parse_str("7b52009b-64fd-0a2a-49e6-d8a939753077").unwrap(),
+            stop_type: StopType::Entry,
+            side: Side::Buy,
+            stop_price: dec!(80.0),
+            size: Some(dec!(2.0)),
+            funds: Some(dec!(50.0)),
+            taker_fee_rate: dec!(0.0025),
+            private: true,
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deserialize_authenticated_match() {
+        let json = "{\"trade_id\":10,\"sequence\":50,\
+                    \"maker_order_id\":\"ac928c66-ca53-498f-9c13-a110027a60e8\",\
+                    \"taker_order_id\":\"132fb6ae-456b-4654-b4e0-d681ac05cea1\",\
+                    \"time\":\"2014-11-07T08:19:27.028459Z\",\"product_id\":\"BTC-USD\",\
+                    \"size\":\"5.23512\",\"price\":\"400.23\",\"side\":\"sell\",\
+                    \"taker_user_id\":\"5844eceecf7e803e259d0365\",\
+                    \"user_id\":\"5844eceecf7e803e259d0365\",\
+                    \"taker_profile_id\":\"765d1549-9660-4be2-97d4-fa2d65fa3352\",\
+                    \"profile_id\":\"765d1549-9660-4be2-97d4-fa2d65fa3352\",\
+                    \"taker_fee_rate\":\"0.005\"}";
+
+        let result: Match = serde_json::from_str(json).unwrap();
+        let profile_id = Uuid::parse_str("765d1549-9660-4be2-97d4-fa2d65fa3352").unwrap();
+
+        assert_eq!(result.user_id, Some(String::from("5844eceecf7e803e259d0365")));
+        assert_eq!(result.profile_id, Some(profile_id));
+        assert_eq!(result.taker_user_id, result.user_id);
+        assert_eq!(result.taker_profile_id, Some(profile_id));
+        assert_eq!(result.maker_user_id, None);
+        assert_eq!(result.taker_fee_rate, Some(dec!(0.005)));
+    }
+}
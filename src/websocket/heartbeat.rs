@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+
+/// The message sent every second on the `heartbeat` channel, the sequence and the last trade
+/// id can be used to detect the missed messages.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct Heartbeat {
+    pub sequence: usize,
+    pub last_trade_id: usize,
+    pub product_id: String,
+    pub time: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use serde_json;
+
+    use super::Heartbeat;
+
+    #[test]
+    fn test_deserialize() {
+        let json = "{\"sequence\":90,\"last_trade_id\":20,\"product_id\":\"BTC-USD\",\
+                    \"time\":\"2014-11-07T08:19:28.464459Z\"}";
+
+        let result: Heartbeat = serde_json::from_str(json).unwrap();
+        let expected = Heartbeat {
+            sequence: 90,
+            last_trade_id: 20,
+            product_id: String::from("BTC-USD"),
+            time: Utc.ymd(2014, 11, 7).and_hms_micro(8, 19, 28, 464_459),
+        };
+
+        assert_eq!(result, expected);
+    }
+}
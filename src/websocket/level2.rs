@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+
+use products::Side;
+use serde_util::deserialize_from_str;
+
+/// The first message of the `level2` channel, it contains all the aggregated price levels of
+/// the book of a product.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct Snapshot {
+    pub product_id: String,
+    pub bids: Vec<SnapshotLevel>,
+    pub asks: Vec<SnapshotLevel>,
+}
+
+/// A price level of a `Snapshot`, GDAX sends it as a `[price, size]` array.
+#[derive(Deserialize, PartialEq, Debug, Copy, Clone)]
+pub struct SnapshotLevel {
+    #[serde(deserialize_with = "deserialize_from_str")] pub price: Decimal,
+    #[serde(deserialize_with = "deserialize_from_str")] pub size: Decimal,
+}
+
+/// The updates of the `level2` channel sent after the `Snapshot`.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct L2Update {
+    pub product_id: String,
+    pub time: Option<DateTime<Utc>>,
+    pub changes: Vec<L2Change>,
+}
+
+/// The new aggregated size of a price level, GDAX sends it as a `[side, price, size]` array.
+/// A size of 0 means that the price level is removed from the book.
+#[derive(Deserialize, PartialEq, Debug, Copy, Clone)]
+pub struct L2Change {
+    pub side: Side,
+    #[serde(deserialize_with = "deserialize_from_str")] pub price: Decimal,
+    #[serde(deserialize_with = "deserialize_from_str")] pub size: Decimal,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use serde_json;
+
+    use products::Side;
+    use super::{L2Change, L2Update, Snapshot, SnapshotLevel};
+
+    #[test]
+    fn test_deserialize_snapshot() {
+        let json = "{\"product_id\":\"BTC-EUR\",\"bids\":[[\"6500.11\",\"0.45054140\"]],\
+                    \"asks\":[[\"6500.15\",\"0.57753524\"],[\"6504.38\",\"0.5\"]]}";
+
+        let result: Snapshot = serde_json::from_str(json).unwrap();
+        let expected = Snapshot {
+            product_id: String::from("BTC-EUR"),
+            bids: vec![
+                SnapshotLevel {
+                    price: dec!(6500.11),
+                    size: dec!(0.4505414),
+                },
+            ],
+            asks: vec![
+                SnapshotLevel {
+                    price: dec!(6500.15),
+                    size: dec!(0.57753524),
+                },
+                SnapshotLevel {
+                    price: dec!(6504.38),
+                    size: dec!(0.5),
+                },
+            ],
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deserialize_l2update() {
+        let json = "{\"product_id\":\"BTC-EUR\",\"time\":\"2017-09-02T17:05:49.250000Z\",\
+                    \"changes\":[[\"buy\",\"6500.09\",\"0.84702376\"],[\"sell\",\"6507.00\",\
+                    \"0\"]]}";
+
+        let result: L2Update = serde_json::from_str(json).unwrap();
+        let expected = L2Update {
+            product_id: String::from("BTC-EUR"),
+            time: Some(Utc.ymd(2017, 9, 2).and_hms_milli(17, 5, 49, 250)),
+            changes: vec![
+                L2Change {
+                    side: Side::Buy,
+                    price: dec!(6500.09),
+                    size: dec!(0.84702376),
+                },
+                L2Change {
+                    side: Side::Sell,
+                    price: dec!(6507.0),
+                    size: dec!(0.0),
+                },
+            ],
+        };
+
+        assert_eq!(result, expected);
+    }
+}
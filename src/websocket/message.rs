@@ -0,0 +1,157 @@
+use serde::de::{self, Deserialize, Deserializer};
+use serde_json::Value;
+
+use super::{Activate, Change, Channel, Done, Heartbeat, L2Update, Match, Open, Received,
+            Snapshot, Status, Ticker};
+
+/// The `type` of the messages that can be parsed into one of the `Message` variants
+const MESSAGE_TYPES: [&str; 13] = [
+    "subscriptions",
+    "heartbeat",
+    "ticker",
+    "snapshot",
+    "l2update",
+    "received",
+    "open",
+    "done",
+    "match",
+    "change",
+    "activate",
+    "status",
+    "error",
+];
+
+/// The messages received from the websocket feed, the variant is selected by the `type` field
+/// (<https://docs.gdax.com/#websocket-feed>).
+///
+/// A message with a type that this crate does not know is returned as `Unknown` with its raw
+/// json content, a message of a known type which cannot be parsed is still an error.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+#[serde(remote = "Message", tag = "type")]
+pub enum Message {
+    #[serde(rename = "subscriptions")] Subscriptions(Subscriptions),
+    #[serde(rename = "heartbeat")] Heartbeat(Heartbeat),
+    #[serde(rename = "ticker")] Ticker(Ticker),
+    #[serde(rename = "snapshot")] Snapshot(Snapshot),
+    #[serde(rename = "l2update")] L2Update(L2Update),
+    #[serde(rename = "received")] Received(Received),
+    #[serde(rename = "open")] Open(Open),
+    #[serde(rename = "done")] Done(Done),
+    #[serde(rename = "match")] Match(Match),
+    #[serde(rename = "change")] Change(Change),
+    #[serde(rename = "activate")] Activate(Activate),
+    #[serde(rename = "status")] Status(Status),
+    #[serde(rename = "error")] Error(ErrorMessage),
+    #[serde(skip_deserializing)] Unknown(Value),
+}
+
+impl<'de> Deserialize<'de> for Message {
+    fn deserialize<D>(deserializer: D) -> Result<Message, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let known = match value.get("type").and_then(Value::as_str) {
+            Some(message_type) => MESSAGE_TYPES.contains(&message_type),
+            None => false,
+        };
+
+        if known {
+            // calls the derived implementation generated by `remote`
+            Message::deserialize(value).map_err(de::Error::custom)
+        } else {
+            Ok(Message::Unknown(value))
+        }
+    }
+}
+
+/// The answer to a subscribe or unsubscribe request, it lists all the current subscriptions.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct Subscriptions {
+    pub channels: Vec<SubscribedChannel>,
+}
+
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct SubscribedChannel {
+    pub name: Channel,
+    pub product_ids: Vec<String>,
+}
+
+/// GDAX has rejected a request of the client
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct ErrorMessage {
+    pub message: String,
+    pub reason: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::{Channel, ErrorMessage, Message, SubscribedChannel, Subscriptions};
+
+    #[test]
+    fn test_deserialize_subscriptions() {
+        let json = "{\"type\":\"subscriptions\",\"channels\":[{\"name\":\"level2\",\
+                    \"product_ids\":[\"ETH-USD\",\"ETH-EUR\"]},{\"name\":\"heartbeat\",\
+                    \"product_ids\":[\"ETH-USD\"]}]}";
+
+        let result: Message = serde_json::from_str(json).unwrap();
+        let expected = Message::Subscriptions(Subscriptions {
+            channels: vec![
+                SubscribedChannel {
+                    name: Channel::Level2,
+                    product_ids: vec![String::from("ETH-USD"), String::from("ETH-EUR")],
+                },
+                SubscribedChannel {
+                    name: Channel::Heartbeat,
+                    product_ids: vec![String::from("ETH-USD")],
+                },
+            ],
+        });
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deserialize_error() {
+        let json = "{\"type\":\"error\",\"message\":\"Failed to subscribe\",\
+                    \"reason\":\"FOO-BAR is not a valid product\"}";
+
+        let result: Message = serde_json::from_str(json).unwrap();
+        let expected = Message::Error(ErrorMessage {
+            message: String::from("Failed to subscribe"),
+            reason: Some(String::from("FOO-BAR is not a valid product")),
+        });
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deserialize_tagged() {
+        let json = "{\"type\":\"l2update\",\"product_id\":\"BTC-EUR\",\
+                    \"changes\":[[\"buy\",\"6500.09\",\"0.84702376\"]]}";
+
+        match serde_json::from_str(json).unwrap() {
+            Message::L2Update(update) => assert_eq!(update.changes.len(), 1),
+            message => panic!("unexpected message {:?}", message),
+        }
+    }
+
+    #[test]
+    fn test_deserialize_unknown() {
+        let json = "{\"type\":\"auction\",\"product_id\":\"BTC-USD\",\"sequence\":12}";
+
+        let result: Message = serde_json::from_str(json).unwrap();
+        let expected = Message::Unknown(serde_json::from_str(json).unwrap());
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deserialize_invalid_known_type() {
+        let json = "{\"type\":\"heartbeat\",\"sequence\":\"not a number\"}";
+
+        assert!(serde_json::from_str::<Message>(json).is_err());
+    }
+}
@@ -0,0 +1,22 @@
+//! This module contains the client of the GDAX websocket feed and the messages it sends and
+//! receives, see "Websocket Feed" section (<https://docs.gdax.com/#websocket-feed>)
+
+mod client;
+mod message;
+mod request;
+mod heartbeat;
+mod ticker;
+mod level2;
+mod full;
+mod status;
+mod user;
+
+pub use self::client::{Feed, WebSocketClient, SANDBOX_WEBSOCKET_FEED, WEBSOCKET_FEED};
+pub use self::message::{ErrorMessage, Message, SubscribedChannel, Subscriptions};
+pub use self::request::{Authentication, Channel, Request};
+pub use self::heartbeat::Heartbeat;
+pub use self::ticker::Ticker;
+pub use self::level2::{L2Change, L2Update, Snapshot, SnapshotLevel};
+pub use self::full::{Activate, Change, Done, DoneReason, Match, Open, Received, StopType};
+pub use self::status::{CurrencyStatus, ProductStatus, Status};
+pub use self::user::OrderUpdate;
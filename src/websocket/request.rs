@@ -0,0 +1,162 @@
+use chrono::Utc;
+use hyper::Method;
+
+use auth::Credentials;
+
+/// The channels of the websocket feed (<https://docs.gdax.com/#channels>).
+#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone)]
+pub enum Channel {
+    #[serde(rename = "heartbeat")] Heartbeat,
+    #[serde(rename = "ticker")] Ticker,
+    #[serde(rename = "level2")] Level2,
+    #[serde(rename = "matches")] Matches,
+    #[serde(rename = "full")] Full,
+    #[serde(rename = "user")] User,
+    #[serde(rename = "status")] Status,
+}
+
+/// The messages sent by the client to change its subscriptions
+/// (<https://docs.gdax.com/#subscribe>), the channels are subscribed for all the given products.
+#[derive(Serialize, PartialEq, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum Request {
+    #[serde(rename = "subscribe")]
+    Subscribe {
+        product_ids: Vec<String>,
+        channels: Vec<Channel>,
+        #[serde(flatten, skip_serializing_if = "Option::is_none")]
+        authentication: Option<Authentication>,
+    },
+    #[serde(rename = "unsubscribe")]
+    Unsubscribe {
+        product_ids: Vec<String>,
+        channels: Vec<Channel>,
+    },
+}
+
+impl Request {
+    pub fn subscribe(product_ids: Vec<String>, channels: Vec<Channel>) -> Request {
+        Request::Subscribe {
+            product_ids,
+            channels,
+            authentication: None,
+        }
+    }
+
+    /// Sign a subscription with `credentials`, it is required to subscribe to the `user`
+    /// channel and to receive the user ids of our orders on the `full` channel. The
+    /// unsubscriptions are not signed.
+    pub fn authenticate(self, credentials: &Credentials) -> Request {
+        let timestamp = Utc::now().timestamp().to_string();
+        self.authenticate_at(credentials, timestamp)
+    }
+
+    fn authenticate_at(self, credentials: &Credentials, timestamp: String) -> Request {
+        match self {
+            Request::Subscribe {
+                product_ids,
+                channels,
+                ..
+            } => Request::Subscribe {
+                product_ids,
+                channels,
+                authentication: Some(Authentication::new(credentials, timestamp)),
+            },
+            unsubscribe => unsubscribe,
+        }
+    }
+
+    /// `true` if the request is a subscription without authentication
+    pub(crate) fn is_unsigned_subscription(&self) -> bool {
+        match *self {
+            Request::Subscribe {
+                ref authentication, ..
+            } => authentication.is_none(),
+            Request::Unsubscribe { .. } => false,
+        }
+    }
+
+    pub fn unsubscribe(product_ids: Vec<String>, channels: Vec<Channel>) -> Request {
+        Request::Unsubscribe {
+            product_ids,
+            channels,
+        }
+    }
+}
+
+/// The authentication fields of a subscription, the signature is the one of a
+/// `GET /users/self/verify` request (<https://docs.gdax.com/#subscribe>).
+#[derive(Serialize, PartialEq, Debug, Clone)]
+pub struct Authentication {
+    signature: String,
+    key: String,
+    passphrase: String,
+    timestamp: String,
+}
+
+impl Authentication {
+    fn new(credentials: &Credentials, timestamp: String) -> Authentication {
+        Authentication {
+            signature: credentials.sign(&timestamp, &Method::Get, "/users/self/verify", ""),
+            key: String::from(credentials.key()),
+            passphrase: String::from(credentials.passphrase()),
+            timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use auth::Credentials;
+    use super::{Channel, Request};
+
+    #[test]
+    fn test_serialize_subscribe() {
+        let request = Request::subscribe(
+            vec![String::from("BTC-USD"), String::from("ETH-USD")],
+            vec![Channel::Heartbeat, Channel::Level2],
+        );
+
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            "{\"type\":\"subscribe\",\"product_ids\":[\"BTC-USD\",\"ETH-USD\"],\
+             \"channels\":[\"heartbeat\",\"level2\"]}"
+        );
+    }
+
+    #[test]
+    fn test_serialize_unsubscribe() {
+        let request = Request::unsubscribe(vec![String::from("BTC-USD")], vec![Channel::Ticker]);
+
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            "{\"type\":\"unsubscribe\",\"product_ids\":[\"BTC-USD\"],\"channels\":[\"ticker\"]}"
+        );
+    }
+
+    #[test]
+    fn test_serialize_authenticated_subscribe() {
+        let credentials = Credentials::new("key", "Z2RheF9ycyBzZWNyZXQga2V5", "pass").unwrap();
+        let request = Request::subscribe(vec![String::from("BTC-USD")], vec![Channel::User])
+            .authenticate_at(&credentials, String::from("1514764800"));
+
+        assert!(!request.is_unsigned_subscription());
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            "{\"type\":\"subscribe\",\"product_ids\":[\"BTC-USD\"],\"channels\":[\"user\"],\
+             \"signature\":\"jQN4IKlGKhTJf/8RH3aQtP3O1y5nKw8fS+/aZ/pP8Jw=\",\"key\":\"key\",\
+             \"passphrase\":\"pass\",\"timestamp\":\"1514764800\"}"
+        );
+    }
+
+    #[test]
+    fn test_unsubscribe_is_not_signed() {
+        let credentials = Credentials::new("key", "Z2RheF9ycyBzZWNyZXQga2V5", "pass").unwrap();
+        let request = Request::unsubscribe(vec![String::from("BTC-USD")], vec![Channel::User]);
+
+        assert_eq!(request.clone().authenticate(&credentials), request);
+        assert!(!request.is_unsigned_subscription());
+    }
+}
@@ -0,0 +1,101 @@
+use rust_decimal::Decimal;
+
+use serde_util::{deserialize_from_str, deserialize_option_from_str};
+
+/// The message of the `status` channel, it is sent every few seconds with the state of all the
+/// products and currencies.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct Status {
+    pub products: Vec<ProductStatus>,
+    pub currencies: Vec<CurrencyStatus>,
+}
+
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct ProductStatus {
+    pub id: String,
+    pub base_currency: String,
+    pub quote_currency: String,
+    #[serde(deserialize_with = "deserialize_from_str")] pub base_min_size: Decimal,
+    #[serde(deserialize_with = "deserialize_from_str")] pub base_max_size: Decimal,
+    #[serde(deserialize_with = "deserialize_from_str")] pub base_increment: Decimal,
+    #[serde(deserialize_with = "deserialize_from_str")] pub quote_increment: Decimal,
+    pub display_name: String,
+    pub status: String,
+    pub status_message: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub min_market_funds: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub max_market_funds: Option<Decimal>,
+    #[serde(default)] pub post_only: bool,
+    #[serde(default)] pub limit_only: bool,
+    #[serde(default)] pub cancel_only: bool,
+}
+
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct CurrencyStatus {
+    pub id: String,
+    pub name: String,
+    #[serde(deserialize_with = "deserialize_from_str")] pub min_size: Decimal,
+    pub status: String,
+    pub status_message: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub max_precision: Option<Decimal>,
+    #[serde(default)] pub convertible_to: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use super::{CurrencyStatus, ProductStatus, Status};
+
+    #[test]
+    fn test_deserialize() {
+        let json = "{\"products\":[{\"id\":\"BTC-USD\",\"base_currency\":\"BTC\",\
+                    \"quote_currency\":\"USD\",\"base_min_size\":\"0.001\",\
+                    \"base_max_size\":\"70\",\"base_increment\":\"0.00000001\",\
+                    \"quote_increment\":\"0.01\",\"display_name\":\"BTC/USD\",\
+                    \"status\":\"online\",\"status_message\":null,\
+                    \"min_market_funds\":\"10\",\"max_market_funds\":\"1000000\",\
+                    \"post_only\":false,\"limit_only\":false,\"cancel_only\":false}],\
+                    \"currencies\":[{\"id\":\"USD\",\"name\":\"United States Dollar\",\
+                    \"min_size\":\"0.01000000\",\"status\":\"online\",\"status_message\":null,\
+                    \"max_precision\":\"0.01\",\"convertible_to\":[\"USDC\"],\"details\":{}}]}";
+
+        let result: Status = serde_json::from_str(json).unwrap();
+        let expected = Status {
+            products: vec![
+                ProductStatus {
+                    id: String::from("BTC-USD"),
+                    base_currency: String::from("BTC"),
+                    quote_currency: String::from("USD"),
+                    base_min_size: dec!(0.001),
+                    base_max_size: dec!(70.0),
+                    base_increment: dec!(0.00000001),
+                    quote_increment: dec!(0.01),
+                    display_name: String::from("BTC/USD"),
+                    status: String::from("online"),
+                    status_message: None,
+                    min_market_funds: Some(dec!(10.0)),
+                    max_market_funds: Some(dec!(1000000.0)),
+                    post_only: false,
+                    limit_only: false,
+                    cancel_only: false,
+                },
+            ],
+            currencies: vec![
+                CurrencyStatus {
+                    id: String::from("USD"),
+                    name: String::from("United States Dollar"),
+                    min_size: dec!(0.01),
+                    status: String::from("online"),
+                    status_message: None,
+                    max_precision: Some(dec!(0.01)),
+                    convertible_to: vec![String::from("USDC")],
+                },
+            ],
+        };
+
+        assert_eq!(result, expected);
+    }
+}
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+
+use products::Side;
+use serde_util::{deserialize_from_str, deserialize_option_from_str};
+
+/// The message sent on the `ticker` channel after each match, the first ticker message of a
+/// product is sent on subscription and has no trade information.
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+pub struct Ticker {
+    pub sequence: usize,
+    pub product_id: String,
+    #[serde(deserialize_with = "deserialize_from_str")] pub price: Decimal,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub open_24h: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub volume_24h: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub low_24h: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub high_24h: Option<Decimal>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub volume_30d: Option<Decimal>,
+    #[serde(deserialize_with = "deserialize_from_str")] pub best_bid: Decimal,
+    #[serde(deserialize_with = "deserialize_from_str")] pub best_ask: Decimal,
+    pub side: Option<Side>,
+    pub time: Option<DateTime<Utc>>,
+    pub trade_id: Option<usize>,
+    #[serde(default, deserialize_with = "deserialize_option_from_str")]
+    pub last_size: Option<Decimal>,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use serde_json;
+
+    use products::Side;
+    use super::Ticker;
+
+    #[test]
+    fn test_deserialize() {
+        let json = "{\"trade_id\":20153558,\"sequence\":3262786978,\
+                    \"time\":\"2017-09-02T17:05:49.250000Z\",\"product_id\":\"BTC-USD\",\
+                    \"price\":\"4388.01000000\",\"side\":\"buy\",\"last_size\":\"0.03000000\",\
+                    \"best_bid\":\"4388\",\"best_ask\":\"4388.01\"}";
+
+        let result: Ticker = serde_json::from_str(json).unwrap();
+        let expected = Ticker {
+            sequence: 3_262_786_978,
+            product_id: String::from("BTC-USD"),
+            price: dec!(4388.01),
+            open_24h: None,
+            volume_24h: None,
+            low_24h: None,
+            high_24h: None,
+            volume_30d: None,
+            best_bid: dec!(4388.0),
+            best_ask: dec!(4388.01),
+            side: Some(Side::Buy),
+            time: Some(Utc.ymd(2017, 9, 2).and_hms_milli(17, 5, 49, 250)),
+            trade_id: Some(20_153_558),
+            last_size: Some(dec!(0.03)),
+        };
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_deserialize_first_ticker() {
+        let json = "{\"sequence\":5928281084,\"product_id\":\"BTC-USD\",\"price\":\"8500.00\",\
+                    \"open_24h\":\"8300.00\",\"volume_24h\":\"12345.6\",\"low_24h\":\"8200.00\",\
+                    \"high_24h\":\"8600.00\",\"volume_30d\":\"300000.5\",\"best_bid\":\"8499.99\",\
+                    \"best_ask\":\"8500.00\"}";
+
+        let result: Ticker = serde_json::from_str(json).unwrap();
+
+        assert_eq!(result.open_24h, Some(dec!(8300.0)));
+        assert_eq!(result.volume_30d, Some(dec!(300000.5)));
+        assert_eq!(result.side, None);
+        assert_eq!(result.trade_id, None);
+        assert_eq!(result.last_size, None);
+    }
+}
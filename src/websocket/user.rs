@@ -0,0 +1,99 @@
+use uuid::Uuid;
+
+use super::{Activate, Change, Done, Match, Message, Open, Received};
+
+/// An event in the life of an order, as sent on the `user` channel for our own orders or on
+/// the `full` channel for all the orders.
+#[derive(PartialEq, Debug, Clone)]
+pub enum OrderUpdate {
+    Received(Received),
+    Open(Open),
+    Done(Done),
+    Match(Match),
+    Change(Change),
+    Activate(Activate),
+}
+
+impl OrderUpdate {
+    /// Returns the update of a message of the feed, or `None` if it is not an order event.
+    pub fn from_message(message: Message) -> Option<OrderUpdate> {
+        match message {
+            Message::Received(received) => Some(OrderUpdate::Received(received)),
+            Message::Open(open) => Some(OrderUpdate::Open(open)),
+            Message::Done(done) => Some(OrderUpdate::Done(done)),
+            Message::Match(match_message) => Some(OrderUpdate::Match(match_message)),
+            Message::Change(change) => Some(OrderUpdate::Change(change)),
+            Message::Activate(activate) => Some(OrderUpdate::Activate(activate)),
+            _ => None,
+        }
+    }
+
+    /// The ids of the orders concerned by the update, a match concerns the maker and the taker
+    /// orders.
+    pub fn order_ids(&self) -> Vec<Uuid> {
+        match *self {
+            OrderUpdate::Received(ref received) => vec![received.order_id],
+            OrderUpdate::Open(ref open) => vec![open.order_id],
+            OrderUpdate::Done(ref done) => vec![done.order_id],
+            OrderUpdate::Match(ref match_message) => {
+                vec![match_message.maker_order_id, match_message.taker_order_id]
+            }
+            OrderUpdate::Change(ref change) => vec![change.order_id],
+            OrderUpdate::Activate(ref activate) => vec![activate.order_id],
+        }
+    }
+
+    pub fn product_id(&self) -> &str {
+        match *self {
+            OrderUpdate::Received(ref received) => &received.product_id,
+            OrderUpdate::Open(ref open) => &open.product_id,
+            OrderUpdate::Done(ref done) => &done.product_id,
+            OrderUpdate::Match(ref match_message) => &match_message.product_id,
+            OrderUpdate::Change(ref change) => &change.product_id,
+            OrderUpdate::Activate(ref activate) => &activate.product_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use uuid::Uuid;
+
+    use super::{Message, OrderUpdate};
+
+    #[test]
+    fn test_from_message() {
+        let message: Message = serde_json::from_str(
+            "{\"type\":\"done\",\"time\":\"2014-11-07T08:19:27.028459Z\",\
+             \"product_id\":\"BTC-USD\",\"sequence\":10,\"price\":\"200.2\",\
+             \"order_id\":\"d50ec984-77a8-460a-b958-66f114b0de9b\",\"reason\":\"canceled\",\
+             \"side\":\"sell\",\"remaining_size\":\"0.5\",\
+             \"user_id\":\"5844eceecf7e803e259d0365\",\
+             \"profile_id\":\"765d1549-9660-4be2-97d4-fa2d65fa3352\"}",
+        ).unwrap();
+
+        let update = OrderUpdate::from_message(message).unwrap();
+        assert_eq!(
+            update.order_ids(),
+            vec![Uuid::parse_str("d50ec984-77a8-460a-b958-66f114b0de9b").unwrap()]
+        );
+        assert_eq!(update.product_id(), "BTC-USD");
+        match update {
+            OrderUpdate::Done(done) => {
+                assert_eq!(done.user_id, Some(String::from("5844eceecf7e803e259d0365")))
+            }
+            update => panic!("unexpected update {:?}", update),
+        }
+    }
+
+    #[test]
+    fn test_from_other_message() {
+        let message: Message = serde_json::from_str(
+            "{\"type\":\"heartbeat\",\"sequence\":90,\"last_trade_id\":20,\
+             \"product_id\":\"BTC-USD\",\"time\":\"2014-11-07T08:19:28.464459Z\"}",
+        ).unwrap();
+
+        assert_eq!(OrderUpdate::from_message(message), None);
+    }
+}